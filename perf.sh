@@ -3,3 +3,10 @@
 cargo build --release
 
 hyperfine --warmup 3 --export-json results/perf.json --parameter-list type rec,rec2,imp "./target/release/waterpouring-{type} 3"
+
+# Record a baseline with `cp results/perf.json results/perf-baseline.json`
+if [ -f results/perf-baseline.json ]; then
+    cargo run --release -p waterpouring-bench --bin perf-report -- --baseline results/perf-baseline.json results/perf.json
+else
+    cargo run --release -p waterpouring-bench --bin perf-report -- results/perf.json
+fi
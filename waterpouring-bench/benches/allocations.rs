@@ -0,0 +1,46 @@
+use waterpouring_bench::alloc::{self, AllocRecord, CountingAllocator};
+use waterpouring_bench::problems;
+use waterpouring_model::problem::Problem;
+use waterpouring_solvers::registry;
+
+// Only this bench counts allocations, so the criterion timings are not slowed down
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// Allocations of one solve for each solver, stored next to the criterion results of the group
+fn record_allocations(group: &str, problems: &[(String, Problem)]) {
+    let mut records = vec![];
+    for (value, problem) in problems {
+        for entry in registry::solvers() {
+            let solver = entry.create();
+            let (_, stats) = alloc::measure(|| solver.solve(problem.clone()));
+            println!(
+                "{}/{}/{}: {} allocations, {} bytes, peak {} bytes",
+                group, entry.info.name, value, stats.allocations, stats.bytes, stats.peak
+            );
+            records.push(AllocRecord {
+                function: entry.info.name.to_string(),
+                value: value.clone(),
+                stats,
+            });
+        }
+    }
+    if let Err(err) = alloc::write_records(&alloc::criterion_dir(), group, &records) {
+        eprintln!("Cannot write allocations of {}: {}", group, err);
+    }
+}
+
+// Values are the criterion benchmark ids, to match the timings
+fn main() {
+    let classic: Vec<(String, Problem)> = problems::classic()
+        .into_iter()
+        .map(|p| (p.to_string(), p))
+        .collect();
+    record_allocations("waterpouring", &classic);
+
+    let generated: Vec<(String, Problem)> = problems::generated()
+        .into_iter()
+        .map(|(length, p)| (length.to_string(), p))
+        .collect();
+    record_allocations("waterpouring-generated", &generated);
+}
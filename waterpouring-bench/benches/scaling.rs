@@ -0,0 +1,164 @@
+#[macro_use]
+extern crate criterion;
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use criterion::{black_box, BenchmarkId, Criterion, Throughput};
+
+use waterpouring_model::glass::Glass;
+use waterpouring_model::problem::Problem;
+use waterpouring_model::reachability::distances;
+use waterpouring_model::state::State;
+use waterpouring_solvers::registry;
+
+const MAX_SAMPLE: usize = 1_000;
+
+// A bench case: empty glasses, the reachable states, and the farthest one as target
+struct Case {
+    name: String,
+    states: Vec<State>,
+    problem: Problem,
+}
+
+impl Case {
+    fn new(name: String, capacities: &[u32]) -> Self {
+        let from = State::new(capacities.iter().map(|&c| Glass::new_empty(c)).collect());
+        let mut reachable: Vec<(State, usize)> = distances(&from).into_iter().collect();
+        // Deterministic order, farthest states first
+        reachable
+            .sort_by_cached_key(|(state, distance)| (usize::MAX - distance, state.to_string()));
+        let to = reachable[0].0.clone();
+        let states = reachable.into_iter().map(|(state, _)| state).collect();
+
+        Self {
+            name,
+            states,
+            problem: Problem::new(from, to),
+        }
+    }
+
+    fn sample(&self) -> &[State] {
+        &self.states[..self.states.len().min(MAX_SAMPLE)]
+    }
+}
+
+const CAPACITIES: [u32; 6] = [2, 3, 4, 5, 7, 9];
+
+fn by_glass_count() -> Vec<Case> {
+    (2..=6)
+        .map(|count| Case::new(count.to_string(), &CAPACITIES[..count]))
+        .collect()
+}
+
+fn by_capacity() -> Vec<Case> {
+    [4, 8, 16, 32, 64]
+        .iter()
+        .map(|&magnitude| {
+            let capacities = [magnitude + 1, magnitude * 2 / 3 + 1, magnitude / 3];
+            Case::new(magnitude.to_string(), &capacities)
+        })
+        .collect()
+}
+
+fn bench_operations(crit: &mut Criterion, group_name: &str, cases: &[Case]) {
+    let mut group = crit.benchmark_group(format!("{}/available_operations", group_name));
+    for case in cases {
+        let sample = case.sample();
+        group.throughput(Throughput::Elements(sample.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(&case.name),
+            &sample,
+            |b, states| {
+                b.iter(|| {
+                    for state in states.iter() {
+                        black_box(state.available_operations());
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+
+    let mut group = crit.benchmark_group(format!("{}/apply", group_name));
+    for case in cases {
+        let moves: Vec<_> = case
+            .sample()
+            .iter()
+            .flat_map(|state| {
+                state
+                    .available_operations()
+                    .into_iter()
+                    .map(move |op| (state, op))
+            })
+            .collect();
+        group.throughput(Throughput::Elements(moves.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(&case.name),
+            &moves,
+            |b, moves| {
+                b.iter(|| {
+                    for (state, op) in moves {
+                        black_box(state.apply(*op));
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+
+    let mut group = crit.benchmark_group(format!("{}/hash", group_name));
+    for case in cases {
+        let sample = case.sample();
+        group.throughput(Throughput::Elements(sample.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(&case.name),
+            &sample,
+            |b, states| {
+                b.iter(|| {
+                    for state in states.iter() {
+                        let mut hasher = DefaultHasher::new();
+                        state.hash(&mut hasher);
+                        black_box(hasher.finish());
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+// Reaching the farthest state visits the whole state graph
+fn bench_solves(crit: &mut Criterion, group_name: &str, cases: &[Case]) {
+    let mut group = crit.benchmark_group(format!("{}/solve", group_name));
+    group.sample_size(10);
+    for case in cases {
+        group.throughput(Throughput::Elements(case.states.len() as u64));
+        for entry in registry::solvers() {
+            group.bench_with_input(
+                BenchmarkId::new(entry.info.name, &case.name),
+                &case.problem,
+                |b, problem| {
+                    let solver = entry.create();
+                    b.iter(|| solver.solve(problem.clone()).expect("Should work"))
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+pub fn glasses_benchmark(crit: &mut Criterion) {
+    let cases = by_glass_count();
+    bench_operations(crit, "glasses", &cases);
+    bench_solves(crit, "glasses", &cases);
+}
+
+pub fn capacity_benchmark(crit: &mut Criterion) {
+    let cases = by_capacity();
+    bench_operations(crit, "capacity", &cases);
+    bench_solves(crit, "capacity", &cases);
+}
+
+criterion_group!(benches, glasses_benchmark, capacity_benchmark);
+criterion_main!(benches);
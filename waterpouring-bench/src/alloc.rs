@@ -0,0 +1,182 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde_derive::{Deserialize, Serialize};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+// Wraps the system allocator, to be registered with `#[global_allocator]` in a bench or binary
+pub struct CountingAllocator;
+
+fn grow(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size, Ordering::Relaxed);
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+fn shrink(size: usize) {
+    CURRENT.fetch_sub(size, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        shrink(layout.size());
+    }
+
+    // A reallocation counts as a new allocation of the new size
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            shrink(layout.size());
+            grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub bytes: usize,
+    // Highest heap usage above the heap in use when the measure started
+    pub peak: usize,
+}
+
+// Counters are global: measures are only meaningful when nothing else runs at the same time
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, AllocStats) {
+    let allocations = ALLOCATIONS.load(Ordering::SeqCst);
+    let bytes = BYTES.load(Ordering::SeqCst);
+    let current = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(current, Ordering::SeqCst);
+
+    let result = f();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::SeqCst) - allocations,
+        bytes: BYTES.load(Ordering::SeqCst) - bytes,
+        peak: PEAK.load(Ordering::SeqCst).saturating_sub(current),
+    };
+    (result, stats)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AllocRecord {
+    pub function: String,
+    pub value: String,
+    pub stats: AllocStats,
+}
+
+// Same place as criterion: `$CARGO_TARGET_DIR/criterion` or `target/criterion`, relative to the
+// bench crate where `cargo bench` runs, so other crates find the results too
+pub fn criterion_dir() -> PathBuf {
+    let target =
+        std::env::var_os("CARGO_TARGET_DIR").map_or_else(|| PathBuf::from("target"), PathBuf::from);
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join(target)
+        .join("criterion")
+}
+
+pub fn records_path(criterion: &Path, group: &str) -> PathBuf {
+    criterion.join(group).join("allocations.json")
+}
+
+pub fn write_records(criterion: &Path, group: &str, records: &[AllocRecord]) -> io::Result<()> {
+    let path = records_path(criterion, group);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string_pretty(records)?;
+    fs::write(path, json)
+}
+
+pub fn read_records(criterion: &Path, group: &str) -> io::Result<Vec<AllocRecord>> {
+    let json = fs::read_to_string(records_path(criterion, group))?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    mod measure {
+        use super::*;
+
+        // Other tests may allocate concurrently, so only lower bounds are checked
+        #[test]
+        fn count_allocations() {
+            let (vector, stats) = measure(|| vec![0_u8; 4096]);
+
+            assert_eq!(vector.len(), 4096);
+            assert!(stats.allocations >= 1);
+            assert!(stats.bytes >= 4096);
+            assert!(stats.peak >= 4096);
+        }
+
+        #[test]
+        fn count_reallocations() {
+            let (_, stats) = measure(|| {
+                let mut vector: Vec<u64> = Vec::with_capacity(1);
+                for i in 0..100 {
+                    vector.push(i);
+                }
+            });
+
+            assert!(stats.allocations >= 2);
+            assert!(stats.bytes >= 800);
+        }
+    }
+
+    mod records {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn write_then_read() {
+            let dir =
+                std::env::temp_dir().join(format!("waterpouring-alloc-{}", std::process::id()));
+            let records = vec![AllocRecord {
+                function: "imp".into(),
+                value: "0/5, 0/3 -> 4/5, 0/3".into(),
+                stats: AllocStats {
+                    allocations: 12,
+                    bytes: 1024,
+                    peak: 512,
+                },
+            }];
+
+            write_records(&dir, "group", &records).unwrap();
+            let result = read_records(&dir, "group").unwrap();
+            fs::remove_dir_all(&dir).unwrap();
+
+            assert_eq!(result, records);
+        }
+    }
+}
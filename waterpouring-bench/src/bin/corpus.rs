@@ -0,0 +1,55 @@
+use std::env;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::process;
+
+use waterpouring_bench::generator::{Generator, GeneratorConfig};
+
+fn usage() -> ! {
+    eprintln!("Usage: corpus [seed] [count] [output]");
+    eprintln!("       defaults to seed 42, 100 problems written into results/corpus.txt");
+    process::exit(1)
+}
+
+fn main() -> std::io::Result<()> {
+    let mut args = env::args().skip(1);
+    let seed: u64 = args
+        .next()
+        .map_or(42, |s| s.parse().unwrap_or_else(|_| usage()));
+    let count: usize = args
+        .next()
+        .map_or(100, |s| s.parse().unwrap_or_else(|_| usage()));
+    let output = args
+        .next()
+        .unwrap_or_else(|| "results/corpus.txt".to_string());
+    if args.next().is_some() {
+        usage();
+    }
+
+    let config = GeneratorConfig {
+        glasses: 2..=4,
+        capacities: 2..=15,
+        solution_length: 2..=12,
+        max_attempts: 100,
+    };
+
+    let mut file = BufWriter::new(File::create(&output)?);
+    writeln!(
+        file,
+        "# seed={} glasses={:?} capacities={:?} length={:?}",
+        seed, config.glasses, config.capacities, config.solution_length
+    )?;
+    // the generator may give up before `count` problems
+    let mut written = 0;
+    for generated in Generator::new(seed, config).take(count) {
+        writeln!(
+            file,
+            "{}\t{}\t{}",
+            generated.solution_length, generated.problem.from, generated.problem.to
+        )?;
+        written += 1;
+    }
+    println!("{} problems written into {}", written, output);
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use std::env;
+use std::process;
+
+use waterpouring_bench::hyperfine::{
+    changes_csv, changes_markdown, summary_csv, summary_markdown, Export,
+};
+
+// Exit codes: 1 on regressions, 2 on invalid arguments or exports
+fn usage() -> ! {
+    eprintln!("Usage: perf-report [--csv] [--baseline <file>] [--threshold <percent>] [export]");
+    eprintln!("       the export defaults to results/perf.json, the threshold to 10%");
+    process::exit(2)
+}
+
+fn main() {
+    let mut csv = false;
+    let mut baseline: Option<String> = None;
+    let mut threshold = 10.0;
+    let mut input: Option<String> = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--csv" => csv = true,
+            "--baseline" => baseline = Some(args.next().unwrap_or_else(|| usage())),
+            "--threshold" => {
+                let value = args.next().unwrap_or_else(|| usage());
+                threshold = value.parse().unwrap_or_else(|_| usage());
+            }
+            flag if flag.starts_with('-') => usage(),
+            _ if input.is_some() => usage(),
+            _ => input = Some(arg),
+        }
+    }
+    let input = input.unwrap_or_else(|| "results/perf.json".to_string());
+
+    let read = |path: &str| {
+        Export::read(path).unwrap_or_else(|err| {
+            eprintln!("{}: {}", path, err);
+            process::exit(2);
+        })
+    };
+
+    let current = read(&input);
+    let summary = current.summary();
+    if csv {
+        print!("{}", summary_csv(&summary));
+    } else {
+        print!("{}", summary_markdown(&summary));
+    }
+
+    if let Some(baseline) = baseline {
+        let changes = current.compare(&read(&baseline), threshold / 100.0);
+        println!();
+        if csv {
+            print!("{}", changes_csv(&changes));
+        } else {
+            print!("{}", changes_markdown(&changes));
+        }
+        if changes.iter().any(|change| change.is_regression()) {
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use std::ops::RangeInclusive;
+
+use rand::seq::IteratorRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use waterpouring_model::glass::Glass;
+use waterpouring_model::problem::Problem;
+use waterpouring_model::reachability::distances;
+use waterpouring_model::state::State;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratorConfig {
+    pub glasses: RangeInclusive<usize>,
+    pub capacities: RangeInclusive<u32>,
+    pub solution_length: RangeInclusive<usize>,
+    pub max_attempts: usize,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            glasses: 2..=3,
+            capacities: 2..=12,
+            solution_length: 4..=8,
+            max_attempts: 100,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedProblem {
+    pub problem: Problem,
+    pub solution_length: usize,
+}
+
+#[derive(Debug)]
+pub struct Generator {
+    config: GeneratorConfig,
+    rng: ChaCha8Rng,
+}
+
+impl Generator {
+    pub fn new(seed: u64, config: GeneratorConfig) -> Self {
+        assert!(*config.glasses.start() >= 2, "Require at least two glasses");
+        assert!(*config.capacities.start() > 0, "Capacity should be > 0");
+        Self {
+            config,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    // Start from empty glasses, and pick a target among the states
+    // reachable with exactly the expected number of operations
+    pub fn generate(&mut self) -> Option<GeneratedProblem> {
+        for _ in 0..self.config.max_attempts {
+            let size = self
+                .rng
+                .gen_range(*self.config.glasses.start(), *self.config.glasses.end() + 1);
+            let glasses: Vec<Glass> = (0..size)
+                .map(|_| {
+                    let capacity = self.rng.gen_range(
+                        *self.config.capacities.start(),
+                        *self.config.capacities.end() + 1,
+                    );
+                    Glass::new_empty(capacity)
+                })
+                .collect();
+            let from = State::new(glasses);
+
+            let mut candidates: Vec<(State, usize)> = distances(&from)
+                .into_iter()
+                .filter(|(_, length)| self.config.solution_length.contains(length))
+                .collect();
+            // HashMap iteration order is random, keep the generation reproducible
+            candidates.sort_by_cached_key(|(state, _)| state.to_string());
+
+            if let Some((to, solution_length)) = candidates.into_iter().choose(&mut self.rng) {
+                return Some(GeneratedProblem {
+                    problem: Problem::new(from, to),
+                    solution_length,
+                });
+            }
+        }
+
+        None
+    }
+}
+
+impl Iterator for Generator {
+    type Item = GeneratedProblem;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.generate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use waterpouring_model::solver::test_solver;
+    use waterpouring_solvers::registry;
+
+    use super::*;
+
+    #[test]
+    fn same_seed_same_problems() {
+        let first: Vec<GeneratedProblem> = Generator::new(42, GeneratorConfig::default())
+            .take(5)
+            .collect();
+        let second: Vec<GeneratedProblem> = Generator::new(42, GeneratorConfig::default())
+            .take(5)
+            .collect();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn respect_config() {
+        let config = GeneratorConfig {
+            glasses: 3..=4,
+            capacities: 3..=7,
+            solution_length: 5..=6,
+            max_attempts: 100,
+        };
+
+        for generated in Generator::new(1, config).take(10) {
+            let glasses = &generated.problem.from;
+            assert!(glasses.len() >= 3 && glasses.len() <= 4);
+            assert!(glasses.iter().all(|g| g.capacity >= 3 && g.capacity <= 7));
+            assert!(generated.solution_length >= 5 && generated.solution_length <= 6);
+        }
+    }
+
+    #[test]
+    fn solution_length_is_optimal() {
+        for generated in Generator::new(7, GeneratorConfig::default()).take(10) {
+            let from = generated.problem.from.to_string();
+            let to = generated.problem.to.to_string();
+
+            assert_eq!(
+                test_solver(&from, &to, registry::default_solver().create().as_ref()),
+                generated.solution_length
+            );
+        }
+    }
+
+    #[test]
+    fn impossible_difficulty() {
+        let config = GeneratorConfig {
+            glasses: 2..=2,
+            capacities: 1..=1,
+            solution_length: 10..=10,
+            max_attempts: 10,
+        };
+
+        assert_eq!(Generator::new(0, config).generate(), None);
+    }
+}
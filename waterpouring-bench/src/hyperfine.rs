@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Error, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde_derive::Deserialize;
+
+#[derive(Debug)]
+pub enum ReportError {
+    Io(io::Error),
+    Json(serde_json::Error),
+}
+
+impl Display for ReportError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            ReportError::Io(err) => write!(f, "Cannot read the hyperfine export: {}", err),
+            ReportError::Json(err) => write!(f, "Invalid hyperfine export: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ReportError {}
+
+// One command measured by hyperfine, times are in seconds
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Run {
+    pub command: String,
+    pub mean: f64,
+    pub stddev: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    #[serde(default)]
+    pub parameters: Option<HashMap<String, String>>,
+}
+
+impl Run {
+    // The `type` parameter of perf.sh, or the binary name without the `waterpouring-` prefix
+    pub fn name(&self) -> String {
+        if let Some(name) = self.parameters.as_ref().and_then(|p| p.get("type")) {
+            return name.clone();
+        }
+        let program = self.command.split_whitespace().next().unwrap_or("");
+        let binary = program.rsplit('/').next().unwrap_or(program);
+        binary
+            .strip_prefix("waterpouring-")
+            .unwrap_or(binary)
+            .to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Export {
+    pub results: Vec<Run>,
+}
+
+impl Export {
+    pub fn parse(json: &str) -> Result<Self, ReportError> {
+        serde_json::from_str(json).map_err(ReportError::Json)
+    }
+
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, ReportError> {
+        let json = fs::read_to_string(path).map_err(ReportError::Io)?;
+        Self::parse(&json)
+    }
+
+    pub fn fastest(&self) -> Option<&Run> {
+        self.results
+            .iter()
+            .min_by(|r1, r2| r1.mean.total_cmp(&r2.mean))
+    }
+
+    // Speedup is relative to the slowest run, so the slowest is 1.0
+    pub fn summary(&self) -> Vec<Summary> {
+        let slowest = self.results.iter().map(|r| r.mean).fold(0.0, f64::max);
+        self.results
+            .iter()
+            .map(|run| Summary {
+                name: run.name(),
+                mean: run.mean,
+                stddev: run.stddev,
+                speedup: slowest / run.mean,
+            })
+            .collect()
+    }
+
+    pub fn compare(&self, baseline: &Export, threshold: f64) -> Vec<Change> {
+        self.results
+            .iter()
+            .filter_map(|run| {
+                let name = run.name();
+                let base = baseline.results.iter().find(|b| b.name() == name)?;
+                Some(Change {
+                    name,
+                    baseline: base.mean,
+                    current: run.mean,
+                    threshold,
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub name: String,
+    pub mean: f64,
+    pub stddev: f64,
+    pub speedup: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub name: String,
+    pub baseline: f64,
+    pub current: f64,
+    // e.g. 0.1 for 10%
+    pub threshold: f64,
+}
+
+impl Change {
+    pub fn ratio(&self) -> f64 {
+        self.current / self.baseline
+    }
+
+    pub fn is_regression(&self) -> bool {
+        self.ratio() > 1.0 + self.threshold
+    }
+
+    pub fn is_improvement(&self) -> bool {
+        self.ratio() < 1.0 - self.threshold
+    }
+
+    fn status(&self) -> &str {
+        if self.is_regression() {
+            "regression"
+        } else if self.is_improvement() {
+            "improvement"
+        } else {
+            "unchanged"
+        }
+    }
+}
+
+fn millis(seconds: f64) -> String {
+    format!("{:.2}", seconds * 1000.0)
+}
+
+pub fn summary_markdown(summary: &[Summary]) -> String {
+    let mut result =
+        String::from("| Solver | Mean (ms) | Std dev (ms) | Speedup |\n|---|---:|---:|---:|\n");
+    for row in summary {
+        result.push_str(&format!(
+            "| {} | {} | ± {} | {:.2}x |\n",
+            row.name,
+            millis(row.mean),
+            millis(row.stddev),
+            row.speedup
+        ));
+    }
+    result
+}
+
+pub fn summary_csv(summary: &[Summary]) -> String {
+    let mut result = String::from("solver,mean_ms,stddev_ms,speedup\n");
+    for row in summary {
+        result.push_str(&format!(
+            "{},{},{},{:.3}\n",
+            row.name,
+            millis(row.mean),
+            millis(row.stddev),
+            row.speedup
+        ));
+    }
+    result
+}
+
+pub fn changes_markdown(changes: &[Change]) -> String {
+    let mut result = String::from(
+        "| Solver | Baseline (ms) | Current (ms) | Change | Status |\n|---|---:|---:|---:|---|\n",
+    );
+    for change in changes {
+        result.push_str(&format!(
+            "| {} | {} | {} | {:+.1}% | {} |\n",
+            change.name,
+            millis(change.baseline),
+            millis(change.current),
+            (change.ratio() - 1.0) * 100.0,
+            change.status()
+        ));
+    }
+    result
+}
+
+pub fn changes_csv(changes: &[Change]) -> String {
+    let mut result = String::from("solver,baseline_ms,current_ms,change_percent,status\n");
+    for change in changes {
+        result.push_str(&format!(
+            "{},{},{},{:.1},{}\n",
+            change.name,
+            millis(change.baseline),
+            millis(change.current),
+            (change.ratio() - 1.0) * 100.0,
+            change.status()
+        ));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn export(means: &[(&str, f64)]) -> Export {
+        let results = means
+            .iter()
+            .map(|(name, mean)| Run {
+                command: format!("./target/release/waterpouring-{} 3", name),
+                mean: *mean,
+                stddev: 0.001,
+                median: *mean,
+                min: *mean,
+                max: *mean,
+                parameters: None,
+            })
+            .collect();
+        Export { results }
+    }
+
+    mod parse {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn read_perf_results() {
+            let export = Export::read(concat!(env!("CARGO_MANIFEST_DIR"), "/../results/perf.json"))
+                .expect("Should be a valid export");
+
+            let names: Vec<String> = export.results.iter().map(Run::name).collect();
+
+            assert_eq!(names, vec!["rec", "rec2", "imp"]);
+        }
+
+        #[test]
+        fn name_from_parameters() {
+            let json = r#"{"results": [{
+                "command": "./target/release/waterpouring-imp 3",
+                "mean": 0.1, "stddev": 0.01, "median": 0.1, "min": 0.09, "max": 0.11,
+                "times": [0.1], "parameters": {"type": "imp"}
+            }]}"#;
+
+            let export = Export::parse(json).unwrap();
+
+            assert_eq!(export.results[0].name(), "imp");
+        }
+
+        #[test]
+        fn invalid_json() {
+            assert!(Export::parse("{}").is_err());
+        }
+    }
+
+    mod summary {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn speedup_against_slowest() {
+            let export = export(&[("rec", 0.004), ("imp", 0.002)]);
+
+            let summary = export.summary();
+
+            assert_eq!(export.fastest().unwrap().name(), "imp");
+            assert_eq!(
+                summary_markdown(&summary),
+                "| Solver | Mean (ms) | Std dev (ms) | Speedup |\n|---|---:|---:|---:|\n\
+                 | rec | 4.00 | ± 1.00 | 1.00x |\n\
+                 | imp | 2.00 | ± 1.00 | 2.00x |\n"
+            );
+            assert_eq!(
+                summary_csv(&summary),
+                "solver,mean_ms,stddev_ms,speedup\nrec,4.00,1.00,1.000\nimp,2.00,1.00,2.000\n"
+            );
+        }
+    }
+
+    mod compare {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn detect_regression() {
+            let baseline = export(&[("rec", 0.010), ("rec2", 0.010), ("imp", 0.010)]);
+            let current = export(&[("rec", 0.012), ("rec2", 0.0105), ("imp", 0.008)]);
+
+            let changes = current.compare(&baseline, 0.1);
+            let statuses: Vec<&str> = changes.iter().map(Change::status).collect();
+
+            assert_eq!(statuses, vec!["regression", "unchanged", "improvement"]);
+            assert_eq!(
+                changes_csv(&changes[..1]),
+                "solver,baseline_ms,current_ms,change_percent,status\n\
+                 rec,10.00,12.00,20.0,regression\n"
+            );
+        }
+
+        #[test]
+        fn ignore_new_runs() {
+            let baseline = export(&[("rec", 0.010)]);
+            let current = export(&[("rec", 0.010), ("imp", 0.001)]);
+
+            assert_eq!(current.compare(&baseline, 0.1).len(), 1);
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod alloc;
+pub mod generator;
+pub mod hyperfine;
+pub mod problems;
@@ -0,0 +1,28 @@
+use waterpouring_model::problem::Problem;
+
+use crate::generator::{Generator, GeneratorConfig};
+
+// Problems of the `waterpouring` bench group
+pub fn classic() -> Vec<Problem> {
+    vec![
+        Problem::from(("0/5, 0/3", "4/5, 0/3")),
+        Problem::from(("12/12, 0/8, 0/5", "6/12, 6/8, 0/5")),
+    ]
+}
+
+// Problems of the `waterpouring-generated` bench group, by solution length
+pub fn generated() -> Vec<(usize, Problem)> {
+    let mut problems: Vec<(usize, Problem)> = vec![];
+    for length in (2..=12).step_by(2) {
+        let config = GeneratorConfig {
+            glasses: 3..=3,
+            capacities: 3..=13,
+            solution_length: length..=length,
+            ..GeneratorConfig::default()
+        };
+        if let Some(generated) = Generator::new(42, config).generate() {
+            problems.push((length, generated.problem));
+        }
+    }
+    problems
+}
@@ -0,0 +1,104 @@
+use proptest::prelude::*;
+
+use waterpouring_model::glass::Glass;
+use waterpouring_model::plan::Plan;
+use waterpouring_model::problem::Warning::AlreadySolved;
+use waterpouring_model::problem::{validate, Problem};
+use waterpouring_model::reachability::distances;
+use waterpouring_model::solver::SolverError::InvalidProblem;
+use waterpouring_model::solver::SolverResult;
+use waterpouring_model::state::State;
+use waterpouring_solvers::registry;
+
+fn glass(capacity: u32) -> impl Strategy<Value = Glass> {
+    (0..=capacity).prop_map(move |current| Glass::new(current, capacity))
+}
+
+fn state(capacities: Vec<u32>) -> impl Strategy<Value = State> {
+    capacities
+        .into_iter()
+        .map(glass)
+        .collect::<Vec<_>>()
+        .prop_map(State::new)
+}
+
+fn valid_problem() -> impl Strategy<Value = Problem> {
+    prop::collection::vec(1..=7u32, 2..=3)
+        .prop_flat_map(|capacities| (state(capacities.clone()), state(capacities)))
+        .prop_map(|(from, to)| Problem::new(from, to))
+}
+
+fn invalid_problem() -> impl Strategy<Value = Problem> {
+    let capacities = prop::collection::vec(1..=7u32, 1..=3);
+    (capacities.clone(), capacities)
+        .prop_filter("Should be an invalid problem", |(from, to)| {
+            from.len() < 2 || from != to
+        })
+        .prop_flat_map(|(from, to)| (state(from), state(to)))
+        .prop_map(|(from, to)| Problem::new(from, to))
+}
+
+fn solve_all(problem: &Problem) -> Vec<(&'static str, SolverResult)> {
+    registry::solvers()
+        .iter()
+        .map(|entry| (entry.info.name, entry.create().solve(problem.clone())))
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn solvers_agree_on_optimal_length(problem in valid_problem()) {
+        let expected = distances(&problem.from)
+            .get(&problem.to)
+            .cloned()
+            .ok_or("UNSOLVABLE");
+
+        for (name, result) in solve_all(&problem) {
+            // unreachable targets must be reported as unsolvable by every solver
+            let length = result
+                .as_ref()
+                .map(|operations| operations.len())
+                .map_err(|err| err.code());
+            prop_assert_eq!(length, expected, "{} on {}", name, problem);
+        }
+    }
+
+    #[test]
+    fn solutions_replay_to_target(problem in valid_problem()) {
+        for (name, result) in solve_all(&problem) {
+            if let Ok(operations) = result {
+                let mut state = problem.from.clone();
+                for &operation in &operations {
+                    state = state.apply(operation);
+                }
+                prop_assert_eq!(&state, &problem.to, "{} on {}", name, problem);
+
+                let plan = Plan::new(problem.clone(), operations);
+                prop_assert!(plan.validate().is_ok(), "{} on {}", name, problem);
+            }
+        }
+    }
+
+    #[test]
+    fn volume_bound_warnings_are_unsolvable(problem in valid_problem()) {
+        let unreachable = validate(&problem).warnings().any(|warning| *warning != AlreadySolved);
+
+        if unreachable {
+            for (name, result) in solve_all(&problem) {
+                prop_assert!(result.is_err(), "{} on {}", name, problem);
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_problems_give_same_reason(problem in invalid_problem()) {
+        let results = solve_all(&problem);
+
+        let (_, expected) = &results[0];
+        let is_invalid = matches!(expected, Err(InvalidProblem { .. }));
+        prop_assert!(is_invalid, "{:?} on {}", expected, problem);
+        for (name, result) in &results {
+            prop_assert_eq!(result, expected, "{} on {}", name, problem);
+        }
+    }
+}
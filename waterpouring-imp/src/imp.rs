@@ -3,9 +3,11 @@ use std::hash::BuildHasher;
 
 use waterpouring_model::operations::Operation;
 use waterpouring_model::problem::{check_solvable, Problem};
+use waterpouring_model::solver::Evidence::Exhausted;
 use waterpouring_model::solver::SolverError::UnsolvableProblem;
-use waterpouring_model::solver::{Solver, SolverResult, StateWithHistory};
+use waterpouring_model::solver::{Limits, Solution, Solver, SolverError, StateWithHistory, Stats};
 use waterpouring_model::state::State;
+use waterpouring_model::symmetry::Symmetry;
 
 #[derive(Debug)]
 pub struct ImperativeSolver();
@@ -14,40 +16,51 @@ impl ImperativeSolver {
     fn process_state_history<S: BuildHasher>(
         new_states_with_history: &mut StateWithHistory,
         visited: &mut HashSet<State, S>,
+        symmetry: &Symmetry,
         state: &State,
         history: &[Operation],
     ) {
         let operations = state.available_operations();
         for op in operations {
             let new_state = state.apply(op);
-            if !visited.contains(&new_state) {
+            // visited states are stored in canonical form
+            if visited.insert(symmetry.canonical(&new_state)) {
                 let mut new_history = history.to_owned();
                 new_history.push(op);
-                new_states_with_history.push((new_state.clone(), new_history));
-                visited.insert(new_state);
+                new_states_with_history.push((new_state, new_history));
             }
         }
     }
 }
 
 impl Solver for ImperativeSolver {
-    fn solve(&self, problem: Problem) -> SolverResult {
+    fn solve_with(&self, problem: Problem, limits: &Limits) -> Result<Solution, SolverError> {
         // Check
         let problem = check_solvable(&problem)?;
 
         // first iteration
         let mut states_with_history: StateWithHistory = vec![(problem.from.clone(), vec![])];
+        let symmetry = limits.symmetry(problem);
         let mut visited: HashSet<State> = HashSet::new();
-        visited.insert(problem.from.clone());
+        visited.insert(symmetry.canonical(&problem.from));
+        let mut depth = 0;
 
         loop {
             let maybe_solution = states_with_history
                 .clone()
                 .into_iter()
                 .find(|(state, _)| *state == problem.to.clone());
+            let stats = Stats {
+                depth,
+                visited: visited.len(),
+            };
             if let Some(result) = maybe_solution {
-                return Ok(result.1);
+                return Ok(Solution {
+                    operations: result.1,
+                    stats,
+                });
             }
+            limits.check(problem, stats)?;
 
             // find next states
             let mut new_states_with_history: StateWithHistory = vec![];
@@ -57,6 +70,7 @@ impl Solver for ImperativeSolver {
                 Self::process_state_history(
                     &mut new_states_with_history,
                     &mut visited,
+                    &symmetry,
                     &state,
                     &history,
                 );
@@ -66,10 +80,14 @@ impl Solver for ImperativeSolver {
             if initial_visited_size == visited.len() {
                 return Err(UnsolvableProblem {
                     problem: problem.to_string(),
+                    evidence: Some(Exhausted {
+                        visited: visited.len(),
+                    }),
                 });
             }
 
             states_with_history = new_states_with_history;
+            depth += 1;
         }
     }
 }
@@ -79,6 +97,7 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use waterpouring_model::solver::test_solver;
+    use waterpouring_model::solver::InvalidReason::GlassCountMismatch;
     use waterpouring_model::solver::SolverError::InvalidProblem;
 
     use super::*;
@@ -105,11 +124,11 @@ mod tests {
         let solver = ImperativeSolver();
         let from = State::from("0/8, 0/4, 0/2");
         let to = State::from("0/4, 0/2");
-        let problem = Problem { from, to };
+        let problem = Problem::new(from, to);
 
         let result = solver.solve(problem.clone());
 
-        let reason = "Should have same number of glasses".to_string();
+        let reason = GlassCountMismatch { from: 3, to: 2 };
         assert_eq!(
             result,
             Err(InvalidProblem {
@@ -124,7 +143,7 @@ mod tests {
         let solver = ImperativeSolver();
         let from = State::from("0/8, 0/4, 0/2");
         let to = State::from("1/8, 0/4, 0/2");
-        let problem = Problem { from, to };
+        let problem = Problem::new(from, to);
 
         let result = solver.solve(problem.clone());
 
@@ -132,7 +151,8 @@ mod tests {
         assert_eq!(
             result,
             Err(UnsolvableProblem {
-                problem: problem.to_string()
+                problem: problem.to_string(),
+                evidence: Some(Exhausted { visited: 30 })
             })
         )
     }
@@ -0,0 +1,288 @@
+use std::io::{Result, Write};
+use std::thread;
+use std::time::Duration;
+
+use crate::glass::Glass;
+use crate::labels::Labels;
+use crate::plan::Trace;
+use crate::state::State;
+
+// Taller glasses are scaled down to this number of rows
+const MAX_HEIGHT: u32 = 12;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AnimationOptions {
+    // wait between two steps, the screen is cleared before each step
+    pub delay: Option<Duration>,
+    // draw the glasses, otherwise only print a line for each step
+    pub tty: bool,
+}
+
+fn scale(value: u32, max_capacity: u32) -> u32 {
+    if max_capacity <= MAX_HEIGHT {
+        value
+    } else {
+        // in `u64` as large capacities would overflow
+        (u64::from(value) * u64::from(MAX_HEIGHT)).div_ceil(u64::from(max_capacity)) as u32
+    }
+}
+
+fn water_rows(glass: &Glass, rows: u32, max_capacity: u32) -> u32 {
+    if glass.is_empty() || glass.is_full() {
+        return if glass.is_empty() { 0 } else { rows };
+    }
+    // a partially filled glass should look neither empty nor full
+    let water = if max_capacity <= MAX_HEIGHT {
+        glass.current
+    } else {
+        (u64::from(glass.current) * u64::from(MAX_HEIGHT) / u64::from(max_capacity)) as u32
+    };
+    water.max(1).min(rows - 1)
+}
+
+fn center(text: &str, width: usize) -> String {
+    let left = (width - text.len()) / 2;
+    let right = width - text.len() - left;
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+}
+
+// Draw glasses side by side, with the level and the capacity below
+pub fn render_state(state: &State) -> String {
+    render_labelled_state(state, &Labels::default())
+}
+
+// Glass names and unit are added below the glasses
+pub fn render_labelled_state(state: &State, labels: &Labels) -> String {
+    let max_capacity = state.iter().map(|g| g.capacity).max().unwrap_or(0);
+    let height = scale(max_capacity, max_capacity);
+    let labels: Vec<String> = state
+        .iter()
+        .enumerate()
+        .map(|(index, glass)| labels.glass(index, glass))
+        .collect();
+    let widths: Vec<usize> = labels.iter().map(|l| l.len().max(3) + 2).collect();
+
+    let mut lines: Vec<String> = vec![];
+    for row in (0..height).rev() {
+        let cells: Vec<String> = state
+            .iter()
+            .zip(widths.iter())
+            .map(|(glass, &width)| {
+                let rows = scale(glass.capacity, max_capacity);
+                let inner = width - 2;
+                if row >= rows {
+                    " ".repeat(width)
+                } else if row < water_rows(glass, rows, max_capacity) {
+                    format!("|{}|", "~".repeat(inner))
+                } else {
+                    format!("|{}|", " ".repeat(inner))
+                }
+            })
+            .collect();
+        lines.push(cells.join("  "));
+    }
+    let bottoms: Vec<String> = widths
+        .iter()
+        .map(|&width| format!("+{}+", "-".repeat(width - 2)))
+        .collect();
+    lines.push(bottoms.join("  "));
+    let captions: Vec<String> = labels
+        .iter()
+        .zip(widths.iter())
+        .map(|(label, &width)| center(label, width))
+        .collect();
+    lines.push(captions.join("  "));
+
+    let lines: Vec<&str> = lines.iter().map(|line| line.trim_end()).collect();
+    format!("{}\n", lines.join("\n"))
+}
+
+pub fn animate<W: Write>(out: &mut W, trace: &Trace, options: &AnimationOptions) -> Result<()> {
+    let labels = trace.labels();
+    if !options.tty {
+        for step in trace {
+            writeln!(
+                out,
+                "{} with {} give {}",
+                labels.state(&step.before),
+                labels.operation(&step.operation),
+                labels.state(&step.after)
+            )?;
+        }
+        return Ok(());
+    }
+
+    let frames = std::iter::once((String::from("Start"), trace.start())).chain(
+        trace.iter().enumerate().map(|(index, step)| {
+            let operation = labels.operation(&step.operation);
+            let title = format!("Step {}/{}: {}", index + 1, trace.len(), operation);
+            (title, &step.after)
+        }),
+    );
+    for (title, state) in frames {
+        if options.delay.is_some() {
+            // clear the screen, and move the cursor to the top left corner
+            write!(out, "\x1b[2J\x1b[H")?;
+        }
+        writeln!(out, "{}", title)?;
+        writeln!(out, "{}", render_labelled_state(state, labels))?;
+        out.flush()?;
+        if let Some(delay) = options.delay {
+            thread::sleep(delay);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod render_state {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn render_glasses() {
+            let state = State::from("4/5, 0/3");
+
+            let result = render_state(&state);
+
+            assert_eq!(
+                result,
+                "|   |\n\
+                 |~~~|\n\
+                 |~~~|  |   |\n\
+                 |~~~|  |   |\n\
+                 |~~~|  |   |\n\
+                 +---+  +---+\n\
+                 \x204/5    0/3\n"
+            );
+        }
+
+        #[test]
+        fn render_large_glasses() {
+            let state = State::from("12/24, 1/13");
+
+            let result = render_state(&state);
+
+            assert_eq!(result.lines().count(), 12 + 2);
+            assert!(result.ends_with("+-----+  +----+\n 12/24    1/13\n"));
+            // the smallest volume is still visible
+            assert!(result.contains("|~~~~~|  |~~~~|"));
+        }
+
+        #[test]
+        fn render_huge_glasses() {
+            let state = State::from("2000000000/4000000000, 0/1000000000");
+
+            let result = render_state(&state);
+
+            assert_eq!(result.lines().count(), 12 + 2);
+        }
+
+        #[test]
+        fn render_labelled_glasses() {
+            let labels = Labels::new(vec![Some("jug".into()), None], Some("L".into()));
+
+            let result = render_labelled_state(&State::from("4/5, 0/3"), &labels);
+
+            assert!(result.ends_with("+--------+  +----+\n jug:4/5L    0/3L\n"));
+        }
+
+        #[test]
+        fn partial_glass_is_not_full() {
+            let state = State::from("23/24, 0/2");
+
+            let result = render_state(&state);
+
+            assert!(result.starts_with("|     |\n"));
+        }
+    }
+
+    mod animate {
+        use pretty_assertions::assert_eq;
+
+        use crate::operations::Operation;
+        use crate::plan::Plan;
+        use crate::problem::Problem;
+
+        use super::*;
+
+        fn trace() -> Trace {
+            let problem = Problem::from(("0/2, 0/1", "1/2, 0/1"));
+            let operations = vec![
+                Operation::fill(0),
+                Operation::pour(0, 1),
+                Operation::empty(1),
+            ];
+            Plan::new(problem, operations).validate().unwrap()
+        }
+
+        #[test]
+        fn plain_output() {
+            let mut out: Vec<u8> = vec![];
+
+            animate(&mut out, &trace(), &AnimationOptions::default()).unwrap();
+
+            assert_eq!(
+                String::from_utf8(out).unwrap(),
+                "0/2, 0/1 with Fill(0) give 2/2, 0/1\n\
+                 2/2, 0/1 with Pour(0->1) give 1/2, 1/1\n\
+                 1/2, 1/1 with Empty(1) give 1/2, 0/1\n"
+            );
+        }
+
+        #[test]
+        fn tty_output() {
+            let mut out: Vec<u8> = vec![];
+            let options = AnimationOptions {
+                delay: None,
+                tty: true,
+            };
+
+            animate(&mut out, &trace(), &options).unwrap();
+
+            let result = String::from_utf8(out).unwrap();
+            assert!(result.starts_with("Start\n|   |\n|   |  |   |\n+---+  +---+\n"));
+            assert!(result.contains("Step 3/3: Empty(1)\n"));
+            assert!(!result.contains('\x1b'));
+        }
+
+        #[test]
+        fn labelled_output() {
+            let problem = Problem::from(("jug:0/2L, cup:0/1L", "1/2, 0/1"));
+            let operations = vec![
+                Operation::fill(0),
+                Operation::pour(0, 1),
+                Operation::empty(1),
+            ];
+            let trace = Plan::new(problem, operations).validate().unwrap();
+            let mut out: Vec<u8> = vec![];
+
+            animate(&mut out, &trace, &AnimationOptions::default()).unwrap();
+
+            let result = String::from_utf8(out).unwrap();
+            assert!(result.starts_with(
+                "jug:0/2L, cup:0/1L with Fill(jug) give jug:2/2L, cup:0/1L\n\
+                 jug:2/2L, cup:0/1L with Pour(jug->cup) give jug:1/2L, cup:1/1L\n"
+            ));
+        }
+
+        #[test]
+        fn clear_screen_with_delay() {
+            let mut out: Vec<u8> = vec![];
+            let options = AnimationOptions {
+                delay: Some(Duration::from_millis(1)),
+                tty: true,
+            };
+
+            animate(&mut out, &trace(), &options).unwrap();
+
+            let result = String::from_utf8(out).unwrap();
+            assert_eq!(result.matches("\x1b[2J").count(), 4);
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use crate::operations::Operation;
+use crate::plan::check_operation;
+use crate::plan::PlanError::{self, NoOp};
+use crate::problem::Problem;
+use crate::solver::{Solver, SolverError};
+use crate::state::State;
+
+// A problem played move by move, with undo and redo
+#[derive(Debug, Clone, PartialEq)]
+pub struct Game {
+    problem: Problem,
+    // states[i] is the state before moves[i], the last one is the current state
+    states: Vec<State>,
+    moves: Vec<Operation>,
+    undone: Vec<Operation>,
+}
+
+impl Game {
+    pub fn new(problem: Problem) -> Self {
+        Self {
+            states: vec![problem.from.clone()],
+            problem,
+            moves: vec![],
+            undone: vec![],
+        }
+    }
+
+    // Restore a game from its moves, e.g. a saved session
+    pub fn replay(problem: Problem, moves: &[Operation]) -> Result<Self, PlanError> {
+        let mut game = Self::new(problem);
+        for &operation in moves {
+            game.apply(operation)?;
+        }
+        Ok(game)
+    }
+
+    pub fn problem(&self) -> &Problem {
+        &self.problem
+    }
+
+    pub fn state(&self) -> &State {
+        self.states.last().unwrap()
+    }
+
+    pub fn moves(&self) -> &[Operation] {
+        &self.moves
+    }
+
+    pub fn available_operations(&self) -> Vec<Operation> {
+        self.state().available_operations()
+    }
+
+    pub fn is_solved(&self) -> bool {
+        *self.state() == self.problem.to
+    }
+
+    // A new move drops the moves that could be redone
+    pub fn apply(&mut self, operation: Operation) -> Result<&State, PlanError> {
+        self.play(operation)?;
+        self.undone.clear();
+        Ok(self.state())
+    }
+
+    fn play(&mut self, operation: Operation) -> Result<(), PlanError> {
+        let index = self.moves.len();
+        check_operation(self.state(), index, operation)?;
+        let next = self.state().apply(operation);
+        if next == *self.state() {
+            return Err(NoOp { index, operation });
+        }
+        self.states.push(next);
+        self.moves.push(operation);
+        Ok(())
+    }
+
+    pub fn undo(&mut self) -> Option<Operation> {
+        let operation = self.moves.pop()?;
+        self.states.pop();
+        self.undone.push(operation);
+        Some(operation)
+    }
+
+    pub fn redo(&mut self) -> Option<Operation> {
+        let operation = self.undone.pop()?;
+        self.play(operation)
+            .expect("An undone move should be valid again");
+        Some(operation)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.moves.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    // Next move of an optimal solution from the current state, `None` when solved
+    pub fn hint<S: Solver + ?Sized>(&self, solver: &S) -> Result<Option<Operation>, SolverError> {
+        let problem = Problem::new(self.state().clone(), self.problem.to.clone())
+            .with_labels(self.problem.labels.clone());
+        let operations = solver.solve(problem)?;
+        Ok(operations.first().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::plan::PlanError::UnknownGlass;
+    use crate::search::Search;
+    use crate::solver::{Limits, Solution};
+
+    use super::*;
+
+    fn game() -> Game {
+        Game::new(Problem::from(("0/5, 0/3", "2/5, 0/3")))
+    }
+
+    struct SearchSolver;
+
+    impl Solver for SearchSolver {
+        fn solve_with(&self, problem: Problem, limits: &Limits) -> Result<Solution, SolverError> {
+            Search::new(problem, *limits).finish()
+        }
+    }
+
+    #[test]
+    fn play_until_solved() {
+        let mut game = game();
+
+        game.apply(Operation::fill(0)).unwrap();
+        game.apply(Operation::pour(0, 1)).unwrap();
+        assert!(!game.is_solved());
+        let state = game.apply(Operation::empty(1)).unwrap();
+
+        assert_eq!(state, &State::from("2/5, 0/3"));
+        assert!(game.is_solved());
+        assert_eq!(game.moves().len(), 3);
+    }
+
+    #[test]
+    fn reject_invalid_moves() {
+        let mut game = game();
+
+        assert_eq!(
+            game.apply(Operation::empty(0)),
+            Err(NoOp {
+                index: 0,
+                operation: Operation::empty(0)
+            })
+        );
+        assert_eq!(
+            game.apply(Operation::fill(2)),
+            Err(UnknownGlass {
+                index: 0,
+                operation: Operation::fill(2)
+            })
+        );
+        assert_eq!(game.state(), &State::from("0/5, 0/3"));
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut game = game();
+        game.apply(Operation::fill(0)).unwrap();
+        game.apply(Operation::pour(0, 1)).unwrap();
+
+        assert_eq!(game.undo(), Some(Operation::pour(0, 1)));
+        assert_eq!(game.state(), &State::from("5/5, 0/3"));
+        assert_eq!(game.redo(), Some(Operation::pour(0, 1)));
+        assert_eq!(game.state(), &State::from("2/5, 3/3"));
+        assert_eq!(game.redo(), None);
+    }
+
+    #[test]
+    fn new_move_clears_redo() {
+        let mut game = game();
+        game.apply(Operation::fill(0)).unwrap();
+        game.undo();
+
+        game.apply(Operation::fill(1)).unwrap();
+
+        assert!(!game.can_redo());
+        assert_eq!(game.undo(), Some(Operation::fill(1)));
+        assert_eq!(game.undo(), None);
+    }
+
+    #[test]
+    fn replay_moves() {
+        let moves = vec![Operation::fill(0), Operation::pour(0, 1)];
+
+        let game = Game::replay(game().problem().clone(), &moves).unwrap();
+
+        assert_eq!(game.state(), &State::from("2/5, 3/3"));
+        assert_eq!(game.moves(), &moves[..]);
+    }
+
+    #[test]
+    fn hint_next_move() {
+        let mut game = game();
+        game.apply(Operation::fill(0)).unwrap();
+
+        assert_eq!(game.hint(&SearchSolver), Ok(Some(Operation::pour(0, 1))));
+
+        game.apply(Operation::pour(0, 1)).unwrap();
+        game.apply(Operation::empty(1)).unwrap();
+        assert_eq!(game.hint(&SearchSolver), Ok(None));
+    }
+}
@@ -2,8 +2,6 @@ use std::cmp::min;
 use std::fmt::{Display, Error, Formatter};
 use std::hash::Hash;
 
-use regex::Regex;
-
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Glass {
     pub capacity: u32,
@@ -42,7 +40,7 @@ impl Glass {
     }
 
     pub fn add(&self, rhs: u32) -> Self {
-        let current = min(self.capacity, self.current + rhs);
+        let current = min(self.capacity, self.current.saturating_add(rhs));
         Self::new(current, self.capacity)
     }
 
@@ -57,17 +55,8 @@ impl Glass {
 
 impl From<&str> for Glass {
     fn from(s: &str) -> Self {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(r"^(?P<current>\d*)/(?P<capacity>\d*)$").unwrap();
-        }
-        let caps = RE
-            .captures(s)
-            .expect("Invalid string, expected something like '2/5'");
-
-        let current: u32 = caps.name("current").unwrap().as_str().parse().unwrap();
-        let capacity: u32 = caps.name("capacity").unwrap().as_str().parse().unwrap();
-
-        Self::new(current, capacity)
+        s.parse()
+            .unwrap_or_else(|err| panic!("Invalid string '{}', {}", s, err))
     }
 }
 
@@ -126,19 +115,19 @@ mod tests {
         #[test]
         #[should_panic]
         fn create_a_glass_from_invalid_string() {
-            Glass::from("3");
+            let _ = Glass::from("3");
         }
 
         #[test]
         #[should_panic]
         fn create_a_glass_from_invalid_string_2() {
-            Glass::from("a/b");
+            let _ = Glass::from("a/b");
         }
 
         #[test]
         #[should_panic]
         fn create_a_glass_from_invalid_string_3() {
-            Glass::from("1/2/3");
+            let _ = Glass::from("1/2/3");
         }
     }
 
@@ -336,6 +325,15 @@ mod tests {
                 }
             );
         }
+
+        #[test]
+        fn glass_plus_quantity_beyond_u32() {
+            let glass = Glass::new(3_000_000_000, 4_000_000_000);
+
+            let result = glass.add(3_000_000_000);
+
+            assert_eq!(result, Glass::new(4_000_000_000, 4_000_000_000));
+        }
     }
 
     mod minus {
@@ -0,0 +1,362 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use crate::labels::Labels;
+use crate::operations::Operation;
+use crate::problem::Problem;
+use crate::state::State;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphOptions {
+    pub max_nodes: usize,
+    // only keep operations that change the state
+    pub collapse_self_loops: bool,
+}
+
+impl Default for GraphOptions {
+    fn default() -> Self {
+        Self {
+            max_nodes: 200,
+            collapse_self_loops: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub operation: Operation,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateGraph {
+    nodes: Vec<State>,
+    edges: Vec<Edge>,
+    truncated: bool,
+}
+
+// Nodes and edges to emphasize in the exported graph
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Highlight {
+    start: Option<State>,
+    goal: Option<State>,
+    path: HashSet<(State, Operation)>,
+    labels: Labels,
+}
+
+impl Highlight {
+    pub fn new(problem: &Problem, solution: &[Operation]) -> Self {
+        let mut path = HashSet::new();
+        let mut state = problem.from.clone();
+        for &operation in solution {
+            let next = state.apply(operation);
+            path.insert((state, operation));
+            state = next;
+        }
+
+        Self {
+            start: Some(problem.from.clone()),
+            goal: Some(problem.to.clone()),
+            path,
+            labels: problem.labels.clone(),
+        }
+    }
+}
+
+// Glass names are free text, quotes must not end the DOT or Mermaid string
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn mermaid_escape(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+impl StateGraph {
+    // Breadth first walk, stop adding nodes after `max_nodes`
+    pub fn explore(from: &State, options: &GraphOptions) -> Self {
+        let mut nodes: Vec<State> = vec![from.clone()];
+        let mut indexes: HashMap<State, usize> = HashMap::new();
+        indexes.insert(from.clone(), 0);
+        let mut edges: Vec<Edge> = vec![];
+        let mut truncated = false;
+
+        let mut current = 0;
+        while current < nodes.len() {
+            let state = nodes[current].clone();
+            let operations = if options.collapse_self_loops {
+                state.available_operations()
+            } else {
+                Operation::all(state.len())
+            };
+            for operation in operations {
+                let next = state.apply(operation);
+                let to = match indexes.get(&next) {
+                    Some(&index) => index,
+                    None if nodes.len() < options.max_nodes => {
+                        indexes.insert(next.clone(), nodes.len());
+                        nodes.push(next);
+                        nodes.len() - 1
+                    }
+                    None => {
+                        truncated = true;
+                        continue;
+                    }
+                };
+                edges.push(Edge {
+                    from: current,
+                    to,
+                    operation,
+                });
+            }
+            current += 1;
+        }
+
+        Self {
+            nodes,
+            edges,
+            truncated,
+        }
+    }
+
+    pub fn nodes(&self) -> &[State] {
+        &self.nodes
+    }
+
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    fn is_on_path(&self, edge: &Edge, highlight: &Highlight) -> bool {
+        highlight
+            .path
+            .contains(&(self.nodes[edge.from].clone(), edge.operation))
+    }
+
+    pub fn to_dot(&self, highlight: &Highlight) -> String {
+        let mut out = String::new();
+        writeln!(out, "digraph waterpouring {{").unwrap();
+        writeln!(out, "  node [shape=box, style=rounded];").unwrap();
+        for (index, state) in self.nodes.iter().enumerate() {
+            let style = if highlight.start.as_ref() == Some(state) {
+                ", style=\"rounded,filled\", fillcolor=lightblue"
+            } else if highlight.goal.as_ref() == Some(state) {
+                ", style=\"rounded,filled\", fillcolor=palegreen"
+            } else {
+                ""
+            };
+            let label = dot_escape(&highlight.labels.state(state));
+            writeln!(out, "  s{} [label=\"{}\"{}];", index, label, style).unwrap();
+        }
+        for edge in &self.edges {
+            let style = if self.is_on_path(edge, highlight) {
+                ", color=red, penwidth=2"
+            } else {
+                ""
+            };
+            writeln!(
+                out,
+                "  s{} -> s{} [label=\"{}\"{}];",
+                edge.from,
+                edge.to,
+                dot_escape(&highlight.labels.operation(&edge.operation)),
+                style
+            )
+            .unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+
+        out
+    }
+
+    pub fn to_mermaid(&self, highlight: &Highlight) -> String {
+        let mut out = String::new();
+        writeln!(out, "graph TD").unwrap();
+        for (index, state) in self.nodes.iter().enumerate() {
+            let label = mermaid_escape(&highlight.labels.state(state));
+            writeln!(out, "  s{}[\"{}\"]", index, label).unwrap();
+        }
+        for edge in &self.edges {
+            writeln!(
+                out,
+                "  s{} -->|\"{}\"| s{}",
+                edge.from,
+                mermaid_escape(&highlight.labels.operation(&edge.operation)),
+                edge.to
+            )
+            .unwrap();
+        }
+        for (index, state) in self.nodes.iter().enumerate() {
+            if highlight.start.as_ref() == Some(state) {
+                writeln!(out, "  style s{} fill:#add8e6", index).unwrap();
+            } else if highlight.goal.as_ref() == Some(state) {
+                writeln!(out, "  style s{} fill:#98fb98", index).unwrap();
+            }
+        }
+        for (index, edge) in self.edges.iter().enumerate() {
+            if self.is_on_path(edge, highlight) {
+                writeln!(out, "  linkStyle {} stroke:red,stroke-width:2px", index).unwrap();
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem() -> Problem {
+        Problem::from(("0/2, 0/1", "1/2, 0/1"))
+    }
+
+    fn solution() -> Vec<Operation> {
+        vec![
+            Operation::fill(0),
+            Operation::pour(0, 1),
+            Operation::empty(1),
+        ]
+    }
+
+    mod explore {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn whole_graph() {
+            let graph = StateGraph::explore(&problem().from, &GraphOptions::default());
+
+            assert_eq!(graph.nodes().len(), 6);
+            assert!(!graph.is_truncated());
+            assert!(graph.edges().iter().all(|edge| edge.from != edge.to));
+        }
+
+        #[test]
+        fn node_cap() {
+            let options = GraphOptions {
+                max_nodes: 3,
+                ..GraphOptions::default()
+            };
+
+            let graph = StateGraph::explore(&problem().from, &options);
+
+            assert_eq!(graph.nodes().len(), 3);
+            assert!(graph.is_truncated());
+            assert!(graph.edges().iter().all(|edge| edge.to < 3));
+        }
+
+        #[test]
+        fn keep_self_loops() {
+            let options = GraphOptions {
+                collapse_self_loops: false,
+                ..GraphOptions::default()
+            };
+
+            let graph = StateGraph::explore(&problem().from, &options);
+
+            assert_eq!(graph.nodes().len(), 6);
+            // 2 fill, 2 empty, 2 pour for each state
+            assert_eq!(graph.edges().len(), 6 * 6);
+            assert!(graph.edges().iter().any(|edge| edge.from == edge.to));
+        }
+    }
+
+    mod export {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn dot() {
+            let options = GraphOptions {
+                max_nodes: 2,
+                ..GraphOptions::default()
+            };
+            let graph = StateGraph::explore(&problem().from, &options);
+            let highlight = Highlight::new(&problem(), &solution());
+
+            let result = graph.to_dot(&highlight);
+
+            assert_eq!(
+                result,
+                "digraph waterpouring {\n\
+                 \x20 node [shape=box, style=rounded];\n\
+                 \x20 s0 [label=\"0/2, 0/1\", style=\"rounded,filled\", fillcolor=lightblue];\n\
+                 \x20 s1 [label=\"2/2, 0/1\"];\n\
+                 \x20 s0 -> s1 [label=\"Fill(0)\", color=red, penwidth=2];\n\
+                 \x20 s1 -> s0 [label=\"Empty(0)\"];\n\
+                 }\n"
+            );
+        }
+
+        #[test]
+        fn mermaid() {
+            let options = GraphOptions {
+                max_nodes: 2,
+                ..GraphOptions::default()
+            };
+            let graph = StateGraph::explore(&problem().from, &options);
+            let highlight = Highlight::new(&problem(), &solution());
+
+            let result = graph.to_mermaid(&highlight);
+
+            assert_eq!(
+                result,
+                "graph TD\n\
+                 \x20 s0[\"0/2, 0/1\"]\n\
+                 \x20 s1[\"2/2, 0/1\"]\n\
+                 \x20 s0 -->|\"Fill(0)\"| s1\n\
+                 \x20 s1 -->|\"Empty(0)\"| s0\n\
+                 \x20 style s0 fill:#add8e6\n\
+                 \x20 linkStyle 0 stroke:red,stroke-width:2px\n"
+            );
+        }
+
+        #[test]
+        fn labelled_nodes() {
+            let problem = Problem::from(("jug:0/2L, cup:0/1L", "1/2, 0/1"));
+            let graph = StateGraph::explore(&problem.from, &GraphOptions::default());
+            let highlight = Highlight::new(&problem, &solution());
+
+            let result = graph.to_mermaid(&highlight);
+
+            assert!(result.contains("  s0[\"jug:0/2L, cup:0/1L\"]\n"));
+            assert!(result.contains("-->|\"Pour(jug->cup)\"|"));
+        }
+
+        #[test]
+        fn escape_labels() {
+            let labels = Labels::new(vec![Some("a\"b\\".into()), None], None);
+            let problem = problem().with_labels(labels);
+            let graph = StateGraph::explore(&problem.from, &GraphOptions::default());
+            let highlight = Highlight::new(&problem, &solution());
+
+            let dot = graph.to_dot(&highlight);
+            let mermaid = graph.to_mermaid(&highlight);
+
+            assert!(dot.contains("[label=\"a\\\"b\\\\:0/2, 0/1\""));
+            assert!(dot.contains("[label=\"Fill(a\\\"b\\\\)\""));
+            assert!(mermaid.contains("  s0[\"a#quot;b\\:0/2, 0/1\"]\n"));
+            assert!(mermaid.contains("-->|\"Fill(a#quot;b\\)\"|"));
+        }
+
+        #[test]
+        fn highlight_goal() {
+            let graph = StateGraph::explore(&problem().from, &GraphOptions::default());
+            let highlight = Highlight::new(&problem(), &solution());
+
+            let result = graph.to_dot(&highlight);
+
+            assert!(result
+                .contains("[label=\"1/2, 0/1\", style=\"rounded,filled\", fillcolor=palegreen]"));
+            assert_eq!(result.matches("color=red").count(), 3);
+        }
+    }
+}
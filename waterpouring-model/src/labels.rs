@@ -0,0 +1,284 @@
+use std::fmt::{Display, Error, Formatter};
+
+use crate::glass::Glass;
+use crate::notation::letter;
+use crate::operations::Operation::{Empty, Fill, Pour};
+use crate::operations::{GlassId, Operation};
+use crate::parse::ParseError;
+use crate::parse::ParseErrorKind::{ConflictingLabels, DuplicateLabel, MixedUnits};
+use crate::state::State;
+
+// A scaled volume with its number of decimals, displayed as written
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Volume {
+    pub value: u64,
+    pub decimals: u32,
+}
+
+impl Volume {
+    pub fn new(value: u64, decimals: u32) -> Self {
+        Self { value, decimals }
+    }
+}
+
+impl From<u32> for Volume {
+    fn from(value: u32) -> Self {
+        Self::new(value.into(), 0)
+    }
+}
+
+impl Display for Volume {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        if self.decimals == 0 {
+            return write!(f, "{}", self.value);
+        }
+        let digits = format!(
+            "{:0>width$}",
+            self.value,
+            width = self.decimals as usize + 1
+        );
+        let (integer, fraction) = digits.split_at(digits.len() - self.decimals as usize);
+        match fraction.trim_end_matches('0') {
+            "" => write!(f, "{}", integer),
+            fraction => write!(f, "{}.{}", integer, fraction),
+        }
+    }
+}
+
+// Optional names of the glasses and unit of the volumes, they only change the display
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Labels {
+    // empty when no glass is named
+    names: Vec<Option<String>>,
+    unit: Option<String>,
+    // volumes are scaled integers, e.g. `2.5` is `25` with one decimal
+    decimals: u32,
+}
+
+impl Labels {
+    pub fn new(names: Vec<Option<String>>, unit: Option<String>) -> Self {
+        // unlabelled problems keep the default labels
+        let names = if names.iter().all(Option::is_none) {
+            vec![]
+        } else {
+            names
+        };
+        Self {
+            names,
+            unit,
+            decimals: 0,
+        }
+    }
+
+    pub fn with_decimals(self, decimals: u32) -> Self {
+        Self { decimals, ..self }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty() && self.unit.is_none() && self.decimals == 0
+    }
+
+    pub fn name(&self, glass: GlassId) -> Option<&str> {
+        self.names.get(glass).and_then(|name| name.as_deref())
+    }
+
+    pub fn names(&self) -> &[Option<String>] {
+        &self.names
+    }
+
+    pub fn unit(&self) -> Option<&str> {
+        self.unit.as_deref()
+    }
+
+    pub fn decimals(&self) -> u32 {
+        self.decimals
+    }
+
+    // The name, or the index for unnamed glasses
+    fn glass_id(&self, glass: GlassId) -> String {
+        self.name(glass)
+            .map(str::to_string)
+            .unwrap_or_else(|| glass.to_string())
+    }
+
+    // The scaled volume as written, e.g. `25` with one decimal is `2.5`
+    pub fn number(&self, volume: u32) -> String {
+        Volume::new(volume.into(), self.decimals).to_string()
+    }
+
+    pub fn volume(&self, volume: u32) -> String {
+        format!("{}{}", self.number(volume), self.unit().unwrap_or_default())
+    }
+
+    // Same syntax as the parser, e.g. `jug:4/5L`
+    pub fn glass(&self, index: GlassId, glass: &Glass) -> String {
+        let text = format!(
+            "{}/{}",
+            self.number(glass.current),
+            self.volume(glass.capacity)
+        );
+        match self.name(index) {
+            Some(name) => format!("{}:{}", name, text),
+            None => text,
+        }
+    }
+
+    pub fn state(&self, state: &State) -> String {
+        let glasses: Vec<String> = state
+            .iter()
+            .enumerate()
+            .map(|(index, glass)| self.glass(index, glass))
+            .collect();
+        glasses.join(", ")
+    }
+
+    // Like the `Operation` display, with glass names, e.g. `Pour(jug->cup)`
+    pub fn operation(&self, operation: &Operation) -> String {
+        match *operation {
+            Empty { glass } => format!("Empty({})", self.glass_id(glass)),
+            Fill { glass } => format!("Fill({})", self.glass_id(glass)),
+            Pour { from, to } => format!("Pour({}->{})", self.glass_id(from), self.glass_id(to)),
+        }
+    }
+
+    // Labels of both states of a problem, they must agree when given on both
+    pub fn merge(&self, other: &Labels) -> Result<Labels, ParseError> {
+        let unit = match (&self.unit, &other.unit) {
+            (Some(first), Some(second)) if first != second => {
+                let kind = MixedUnits {
+                    first: first.clone(),
+                    second: second.clone(),
+                };
+                return Err(ParseError::without_position(kind));
+            }
+            (first, second) => first.clone().or_else(|| second.clone()),
+        };
+
+        let count = self.names.len().max(other.names.len());
+        let mut names: Vec<Option<String>> = vec![];
+        for glass in 0..count {
+            let name = match (self.name(glass), other.name(glass)) {
+                (Some(first), Some(second)) if first != second => {
+                    let kind = ConflictingLabels {
+                        glass,
+                        first: first.to_string(),
+                        second: second.to_string(),
+                    };
+                    return Err(ParseError::without_position(kind));
+                }
+                (first, second) => first.or(second).map(str::to_string),
+            };
+            names.push(name);
+        }
+
+        if let Some((_, name)) = name_clash(&names) {
+            let kind = DuplicateLabel {
+                name: name.to_string(),
+            };
+            return Err(ParseError::without_position(kind));
+        }
+
+        let decimals = self.decimals.max(other.decimals);
+        Ok(Self::new(names, unit).with_decimals(decimals))
+    }
+}
+
+// The first name already taken, ignoring case, by a previous glass or by the letter of an unnamed glass
+pub(crate) fn name_clash(names: &[Option<String>]) -> Option<(GlassId, &str)> {
+    names.iter().enumerate().find_map(|(index, name)| {
+        let name = name.as_deref()?;
+        let taken = names
+            .iter()
+            .enumerate()
+            .any(|(other, candidate)| match candidate {
+                Some(candidate) => other < index && candidate.eq_ignore_ascii_case(name),
+                None => letter(other).eq_ignore_ascii_case(name),
+            });
+        if taken {
+            Some((index, name))
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn labels() -> Labels {
+        Labels::new(vec![Some("jug".into()), None], Some("L".into()))
+    }
+
+    #[test]
+    fn unlabelled() {
+        let labels = Labels::new(vec![None, None], None);
+        let state = State::from("4/5, 0/3");
+
+        assert_eq!(labels, Labels::default());
+        assert!(labels.is_empty());
+        assert_eq!(labels.state(&state), state.to_string());
+        assert_eq!(labels.operation(&Operation::pour(0, 1)), "Pour(0->1)");
+    }
+
+    #[test]
+    fn display_with_labels() {
+        let state = State::from("4/5, 0/3");
+
+        assert_eq!(labels().state(&state), "jug:4/5L, 0/3L");
+        assert_eq!(labels().operation(&Operation::pour(0, 1)), "Pour(jug->1)");
+        assert_eq!(labels().operation(&Operation::fill(0)), "Fill(jug)");
+        assert_eq!(labels().volume(4), "4L");
+    }
+
+    #[test]
+    fn display_decimals() {
+        let labels = Labels::new(vec![], Some("L".into())).with_decimals(2);
+        let state = State::from("100/250, 0/75");
+
+        assert!(!Labels::default().with_decimals(1).is_empty());
+        assert_eq!(labels.state(&state), "1/2.5L, 0/0.75L");
+        assert_eq!(labels.number(5), "0.05");
+        assert_eq!(labels.volume(1210), "12.1L");
+    }
+
+    #[test]
+    fn display_volume() {
+        assert_eq!(Volume::from(15).to_string(), "15");
+        assert_eq!(Volume::new(15, 1).to_string(), "1.5");
+        assert_eq!(Volume::new(3, 2).to_string(), "0.03");
+        assert_eq!(Volume::new(300, 2).to_string(), "3");
+    }
+
+    #[test]
+    fn merge_labels() {
+        let other = Labels::new(vec![None, Some("cup".into())], None);
+
+        let result = labels().merge(&other).unwrap();
+
+        assert_eq!(result.name(0), Some("jug"));
+        assert_eq!(result.name(1), Some("cup"));
+        assert_eq!(result.unit(), Some("L"));
+    }
+
+    #[test]
+    fn conflicting_labels() {
+        let units = Labels::new(vec![], Some("mL".into()));
+        let names = Labels::new(vec![Some("bucket".into())], None);
+
+        assert_eq!(labels().merge(&units).unwrap_err().code(), "MIXED_UNITS");
+        assert_eq!(
+            labels().merge(&names).unwrap_err().to_string(),
+            "glass 0 is named 'jug' and 'bucket'"
+        );
+        assert_eq!(
+            labels()
+                .merge(&Labels::new(vec![None, Some("JUG".into())], None))
+                .unwrap_err()
+                .to_string(),
+            "the name 'JUG' is used by several glasses"
+        );
+    }
+}
@@ -1,8 +1,17 @@
-#[macro_use]
-extern crate lazy_static;
-
+pub mod ascii;
+pub mod game;
 pub mod glass;
+pub mod graph;
+pub mod labels;
+pub mod notation;
 pub mod operations;
+pub mod optimizer;
+pub mod parse;
+pub mod plan;
 pub mod problem;
+pub mod reachability;
+pub mod search;
 pub mod solver;
 pub mod state;
+pub mod svg;
+pub mod symmetry;
@@ -0,0 +1,237 @@
+use crate::operations::Operation::{Empty, Fill, Pour};
+use crate::operations::{GlassId, Operation};
+use crate::parse::ParseErrorKind::{InvalidOperation, SameGlass, UnknownGlass};
+use crate::parse::{leading_spaces, ParseError};
+use crate::problem::Problem;
+
+// Human friendly operations, e.g. `fill A`, `empty B` or `pour A->B`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notation {
+    names: Vec<String>,
+}
+
+// A, B, ..., Z, AA, AB, ...
+pub(crate) fn letter(glass: GlassId) -> String {
+    let mut result = String::new();
+    let mut index = glass + 1;
+    while index > 0 {
+        let rest = (index - 1) % 26;
+        result.insert(0, (b'A' + rest as u8) as char);
+        index = (index - 1) / 26;
+    }
+    result
+}
+
+impl Notation {
+    pub fn letters(glasses: usize) -> Self {
+        Self {
+            names: (0..glasses).map(letter).collect(),
+        }
+    }
+
+    pub fn labels(labels: Vec<String>) -> Self {
+        Self { names: labels }
+    }
+
+    // Glass names of the problem, letters for unnamed glasses
+    pub fn of(problem: &Problem) -> Self {
+        let names = (0..problem.from.len())
+            .map(|glass| {
+                problem
+                    .labels
+                    .name(glass)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| letter(glass))
+            })
+            .collect();
+        Self { names }
+    }
+
+    // Glasses without a name fall back to letters
+    pub fn name(&self, glass: GlassId) -> String {
+        self.names
+            .get(glass)
+            .cloned()
+            .unwrap_or_else(|| letter(glass))
+    }
+
+    // Names are not case sensitive
+    pub fn glass(&self, name: &str) -> Option<GlassId> {
+        self.names
+            .iter()
+            .position(|candidate| candidate.eq_ignore_ascii_case(name))
+    }
+
+    pub fn format(&self, operation: &Operation) -> String {
+        match *operation {
+            Empty { glass } => format!("empty {}", self.name(glass)),
+            Fill { glass } => format!("fill {}", self.name(glass)),
+            Pour { from, to } => format!("pour {}->{}", self.name(from), self.name(to)),
+        }
+    }
+
+    pub fn format_plan(&self, operations: &[Operation]) -> String {
+        let operations: Vec<String> = operations.iter().map(|op| self.format(op)).collect();
+        operations.join(", ")
+    }
+
+    fn parse_glass(&self, s: &str, offset: usize) -> Result<GlassId, ParseError> {
+        let name = s.trim();
+        self.glass(name).ok_or_else(|| {
+            let kind = UnknownGlass {
+                name: name.to_string(),
+            };
+            ParseError::new(kind, offset + leading_spaces(s))
+        })
+    }
+
+    pub fn parse(&self, s: &str) -> Result<Operation, ParseError> {
+        let leading = leading_spaces(s);
+        let text = s.trim();
+        let space = text
+            .find(char::is_whitespace)
+            .ok_or_else(|| ParseError::new(InvalidOperation, leading))?;
+        let (keyword, arguments) = (&text[..space], &text[space..]);
+        let offset = leading + space;
+
+        match keyword.to_lowercase().as_str() {
+            "empty" => Ok(Operation::empty(self.parse_glass(arguments, offset)?)),
+            "fill" => Ok(Operation::fill(self.parse_glass(arguments, offset)?)),
+            "pour" => {
+                let arrow = arguments.find("->").ok_or_else(|| {
+                    ParseError::new(InvalidOperation, offset + leading_spaces(arguments))
+                })?;
+                let from = self.parse_glass(&arguments[..arrow], offset)?;
+                let to = self.parse_glass(&arguments[arrow + 2..], offset + arrow + 2)?;
+                if from == to {
+                    return Err(ParseError::new(SameGlass, leading));
+                }
+                Ok(Operation::pour(from, to))
+            }
+            _ => Err(ParseError::new(InvalidOperation, leading)),
+        }
+    }
+
+    // Operations are separated by `,`, `;` or new lines
+    pub fn parse_plan(&self, s: &str) -> Result<Vec<Operation>, ParseError> {
+        let mut operations: Vec<Operation> = vec![];
+        let mut offset = 0;
+        for item in s.split([',', ';', '\n']) {
+            let start = offset;
+            offset += item.len() + 1;
+            if item.trim().is_empty() {
+                continue;
+            }
+            let operation = self
+                .parse(item)
+                .map_err(|err: ParseError| err.shift(start))?;
+            operations.push(operation);
+        }
+
+        Ok(operations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn letters() {
+        let notation = Notation::letters(3);
+
+        assert_eq!(notation.name(0), "A");
+        assert_eq!(notation.name(2), "C");
+        assert_eq!(letter(25), "Z");
+        assert_eq!(letter(26), "AA");
+        assert_eq!(letter(27), "AB");
+        assert_eq!(notation.glass("b"), Some(1));
+        assert_eq!(notation.glass("D"), None);
+    }
+
+    #[test]
+    fn format_operations() {
+        let notation = Notation::letters(2);
+        let operations = vec![
+            Operation::fill(0),
+            Operation::pour(0, 1),
+            Operation::empty(1),
+        ];
+
+        let result = notation.format_plan(&operations);
+
+        assert_eq!(result, "fill A, pour A->B, empty B");
+        assert_eq!(notation.parse_plan(&result), Ok(operations));
+    }
+
+    #[test]
+    fn custom_labels() {
+        let notation = Notation::labels(vec!["big jug".into(), "cup".into()]);
+
+        assert_eq!(notation.format(&Operation::pour(0, 1)), "pour big jug->cup");
+        assert_eq!(
+            notation.parse_plan("Fill big jug; pour big jug -> cup\nempty CUP"),
+            Ok(vec![
+                Operation::fill(0),
+                Operation::pour(0, 1),
+                Operation::empty(1),
+            ])
+        );
+    }
+
+    #[test]
+    fn names_of_the_problem() {
+        let problem = Problem::from(("jug:0/5, 0/3", "4/5, 0/3"));
+
+        let notation = Notation::of(&problem);
+
+        assert_eq!(notation.format(&Operation::pour(0, 1)), "pour jug->B");
+        assert_eq!(notation, Notation::labels(vec!["jug".into(), "B".into()]));
+    }
+
+    #[test]
+    fn labelled_plans_round_trip() {
+        let problem = Problem::from(("big jug:0/5, jug-1:0/3, 0/2", "4/5, 0/3, 0/2"));
+        let notation = Notation::of(&problem);
+        let operations = vec![
+            Operation::fill(0),
+            Operation::pour(0, 1),
+            Operation::pour(1, 2),
+            Operation::empty(2),
+        ];
+
+        let result = notation.format_plan(&operations);
+
+        assert_eq!(
+            result,
+            "fill big jug, pour big jug->jug-1, pour jug-1->C, empty C"
+        );
+        assert_eq!(notation.parse_plan(&result), Ok(operations));
+    }
+
+    #[test]
+    fn invalid_operations() {
+        let notation = Notation::letters(2);
+        let error = |s: &str| notation.parse_plan(s).unwrap_err();
+
+        assert_eq!(error("drink A"), ParseError::new(InvalidOperation, 0));
+        assert_eq!(error("fill A, fill"), ParseError::new(InvalidOperation, 8));
+        assert_eq!(error("pour A B"), ParseError::new(InvalidOperation, 5));
+        assert_eq!(error("pour A->A"), ParseError::new(SameGlass, 0));
+        assert_eq!(
+            error("fill A, pour A -> C"),
+            ParseError::new(
+                UnknownGlass {
+                    name: "C".to_string()
+                },
+                18
+            )
+        );
+        assert_eq!(
+            error("empty Z").to_string(),
+            "unknown glass 'Z' at position 6"
+        );
+    }
+}
@@ -4,7 +4,7 @@ use crate::operations::Operation::{Empty, Fill, Pour};
 
 pub type GlassId = usize;
 
-#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
 pub enum Operation {
     Empty { glass: GlassId },
     Fill { glass: GlassId },
@@ -23,6 +23,19 @@ impl Operation {
         Pour { from, to }
     }
 
+    // Every syntactically valid operation, even the ones that change nothing
+    pub fn all(glasses: usize) -> Vec<Self> {
+        let mut result: Vec<Self> = vec![];
+        for glass in 0..glasses {
+            result.push(Self::fill(glass));
+            result.push(Self::empty(glass));
+            for to in (0..glasses).filter(|&to| to != glass) {
+                result.push(Self::pour(glass, to));
+            }
+        }
+        result
+    }
+
     fn as_string(&self) -> String {
         match self {
             Empty { glass } => format!("Empty({})", glass),
@@ -106,6 +119,23 @@ mod tests {
         assert_eq!("Pour(0->1)".to_owned(), format!("{}", op))
     }
 
+    #[test]
+    fn all_operations() {
+        let result = Operation::all(2);
+
+        assert_eq!(
+            result,
+            vec![
+                Operation::fill(0),
+                Operation::empty(0),
+                Operation::pour(0, 1),
+                Operation::fill(1),
+                Operation::empty(1),
+                Operation::pour(1, 0),
+            ]
+        )
+    }
+
     #[test]
     #[should_panic]
     fn create_pour_invalid() {
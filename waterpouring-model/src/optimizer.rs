@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Error, Formatter};
+
+use crate::operations::Operation;
+use crate::optimizer::Removal::{Cycle, NoOp};
+use crate::plan::PlanError::TargetNotReached;
+use crate::plan::{check_operation, PlanError};
+use crate::problem::Problem;
+use crate::state::State;
+
+// Indexes refer to the position of the operations in the original plan
+#[derive(Debug, Clone, PartialEq)]
+pub enum Removal {
+    NoOp {
+        index: usize,
+        operation: Operation,
+    },
+    Cycle {
+        start: usize,
+        end: usize,
+        state: State,
+        operations: Vec<Operation>,
+    },
+}
+
+impl Display for Removal {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            NoOp { index, operation } => write!(f, "- [{}] {} changes nothing", index, operation),
+            Cycle {
+                start,
+                end,
+                state,
+                operations,
+            } => {
+                let operations: Vec<String> = operations.iter().map(|op| op.to_string()).collect();
+                write!(
+                    f,
+                    "- [{}..={}] {} come back to {}",
+                    start,
+                    end,
+                    operations.join(" "),
+                    state
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simplification {
+    pub operations: Vec<Operation>,
+    pub removed: Vec<Removal>,
+}
+
+impl Simplification {
+    pub fn is_unchanged(&self) -> bool {
+        self.removed.is_empty()
+    }
+}
+
+impl Display for Simplification {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let operations: Vec<String> = self.operations.iter().map(|op| op.to_string()).collect();
+        writeln!(f, "{}", operations.join(" "))?;
+        for removal in &self.removed {
+            writeln!(f, "{}", removal)?;
+        }
+        Ok(())
+    }
+}
+
+// Drop operations that change nothing, and every cycle that revisit a state
+pub fn simplify(problem: &Problem, operations: &[Operation]) -> Result<Simplification, PlanError> {
+    let mut kept: Vec<(usize, Operation)> = vec![];
+    let mut states: Vec<State> = vec![problem.from.clone()];
+    let mut positions: HashMap<State, usize> = HashMap::new();
+    positions.insert(problem.from.clone(), 0);
+    let mut removed: Vec<Removal> = vec![];
+
+    for (index, &operation) in operations.iter().enumerate() {
+        check_operation(&states[kept.len()], index, operation)?;
+        let next = states[kept.len()].apply(operation);
+
+        if next == states[kept.len()] {
+            removed.push(NoOp { index, operation });
+        } else if let Some(&position) = positions.get(&next) {
+            let start = kept.get(position).map_or(index, |(start, _)| *start);
+            let mut cycle: Vec<Operation> = kept.drain(position..).map(|(_, op)| op).collect();
+            cycle.push(operation);
+            for state in states.drain(position + 1..) {
+                positions.remove(&state);
+            }
+            removed.push(Cycle {
+                start,
+                end: index,
+                state: next,
+                operations: cycle,
+            });
+        } else {
+            kept.push((index, operation));
+            positions.insert(next.clone(), kept.len());
+            states.push(next);
+        }
+    }
+
+    let end = states[kept.len()].clone();
+    if end != problem.to {
+        return Err(TargetNotReached { state: end });
+    }
+
+    Ok(Simplification {
+        operations: kept.into_iter().map(|(_, op)| op).collect(),
+        removed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::operations::Operation::Pour;
+    use crate::plan::Plan;
+    use crate::plan::PlanError::SameGlass;
+
+    use super::*;
+
+    fn problem() -> Problem {
+        Problem::from(("0/5, 0/3", "2/5, 0/3"))
+    }
+
+    fn optimal() -> Vec<Operation> {
+        vec![
+            Operation::fill(0),
+            Operation::pour(0, 1),
+            Operation::empty(1),
+        ]
+    }
+
+    #[test]
+    fn keep_optimal_plan() {
+        let result = simplify(&problem(), &optimal()).unwrap();
+
+        assert!(result.is_unchanged());
+        assert_eq!(result.operations, optimal());
+    }
+
+    #[test]
+    fn remove_no_op() {
+        let operations = vec![
+            Operation::fill(0),
+            Operation::fill(0),
+            Operation::pour(0, 1),
+            Operation::empty(1),
+        ];
+
+        let result = simplify(&problem(), &operations).unwrap();
+
+        assert_eq!(result.operations, optimal());
+        assert_eq!(
+            result.removed,
+            vec![NoOp {
+                index: 1,
+                operation: Operation::fill(0)
+            }]
+        );
+    }
+
+    #[test]
+    fn remove_fill_then_empty() {
+        let operations = vec![
+            Operation::fill(1),
+            Operation::empty(1),
+            Operation::fill(0),
+            Operation::pour(0, 1),
+            Operation::empty(1),
+        ];
+
+        let result = simplify(&problem(), &operations).unwrap();
+
+        assert_eq!(result.operations, optimal());
+        assert_eq!(
+            result.removed,
+            vec![Cycle {
+                start: 0,
+                end: 1,
+                state: State::from("0/5, 0/3"),
+                operations: vec![Operation::fill(1), Operation::empty(1)]
+            }]
+        );
+    }
+
+    #[test]
+    fn remove_nested_cycles() {
+        let operations = vec![
+            Operation::fill(0),
+            Operation::pour(0, 1),
+            Operation::pour(1, 0),
+            Operation::pour(0, 1),
+            Operation::empty(1),
+            Operation::pour(0, 1),
+            Operation::pour(1, 0),
+        ];
+
+        let result = simplify(&problem(), &operations).unwrap();
+
+        assert_eq!(result.operations, optimal());
+        assert_eq!(result.removed.len(), 2);
+        let plan = Plan::new(problem(), result.operations);
+        assert!(plan.validate().is_ok());
+    }
+
+    #[test]
+    fn invalid_operation() {
+        let operation = Pour { from: 1, to: 1 };
+
+        let result = simplify(&problem(), &[operation]);
+
+        assert_eq!(
+            result,
+            Err(SameGlass {
+                index: 0,
+                operation
+            })
+        );
+    }
+
+    #[test]
+    fn target_not_reached() {
+        let result = simplify(&problem(), &[Operation::fill(1), Operation::empty(1)]);
+
+        assert_eq!(
+            result,
+            Err(TargetNotReached {
+                state: State::from("0/5, 0/3")
+            })
+        );
+    }
+
+    #[test]
+    fn display_removals() {
+        let operations = vec![
+            Operation::fill(1),
+            Operation::empty(1),
+            Operation::fill(0),
+            Operation::fill(0),
+            Operation::pour(0, 1),
+            Operation::empty(1),
+        ];
+
+        let result = simplify(&problem(), &operations).unwrap();
+
+        assert_eq!(
+            result.to_string(),
+            "Fill(0) Pour(0->1) Empty(1)\n\
+             - [0..=1] Fill(1) Empty(1) come back to 0/5, 0/3\n\
+             - [3] Fill(0) changes nothing\n"
+        );
+    }
+}
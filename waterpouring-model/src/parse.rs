@@ -0,0 +1,752 @@
+use std::convert::TryFrom;
+use std::fmt::{Display, Error, Formatter};
+use std::str::FromStr;
+
+use crate::glass::Glass;
+use crate::labels::{name_clash, Labels};
+use crate::operations::{GlassId, Operation};
+use crate::parse::ParseErrorKind::{
+    ConflictingLabels, DuplicateLabel, InvalidGlass, InvalidLabel, InvalidNumber, InvalidOperation,
+    MixedUnits, NoGlass, OverflowingGlass, PrecisionOverflow, SameGlass, UnknownGlass,
+    ZeroCapacity,
+};
+use crate::state::State;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    // expected `current/capacity`, or `label:current/capacity` with a unit, e.g. `jug:0/5L`
+    InvalidGlass,
+    InvalidNumber,
+    ZeroCapacity,
+    OverflowingGlass {
+        current: String,
+        capacity: String,
+    },
+    NoGlass,
+    // expected `Empty(0)`, `Fill(0)`, `Pour(0->1)`, or `fill A` with a notation
+    InvalidOperation,
+    UnknownGlass {
+        name: String,
+    },
+    SameGlass,
+    MixedUnits {
+        first: String,
+        second: String,
+    },
+    // names ignore case, and unnamed glasses are called by their letter in the plan notation
+    DuplicateLabel {
+        name: String,
+    },
+    // names are letters, digits, `_`, `-` or inner spaces, but not a number: it would read as an index
+    InvalidLabel {
+        name: String,
+    },
+    // the `from` and `to` states name a glass differently
+    ConflictingLabels {
+        glass: GlassId,
+        first: String,
+        second: String,
+    },
+    // scaled volumes do not fit into a `u32`
+    PrecisionOverflow {
+        decimals: u32,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    // byte offset in the parsed string, `None` when the error is not in a single string
+    pub position: Option<usize>,
+}
+
+impl ParseError {
+    pub(crate) fn new(kind: ParseErrorKind, position: usize) -> Self {
+        Self {
+            kind,
+            position: Some(position),
+        }
+    }
+
+    // e.g. the `from` and `to` states disagree
+    pub(crate) fn without_position(kind: ParseErrorKind) -> Self {
+        Self {
+            kind,
+            position: None,
+        }
+    }
+
+    pub(crate) fn shift(self, offset: usize) -> Self {
+        Self {
+            position: self.position.map(|position| position + offset),
+            ..self
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self.kind {
+            InvalidGlass => "INVALID_GLASS",
+            InvalidNumber => "INVALID_NUMBER",
+            ZeroCapacity => "ZERO_CAPACITY",
+            OverflowingGlass { .. } => "OVERFLOWING_GLASS",
+            NoGlass => "NO_GLASS",
+            InvalidOperation => "INVALID_OPERATION",
+            UnknownGlass { .. } => "UNKNOWN_GLASS",
+            SameGlass => "SAME_GLASS",
+            MixedUnits { .. } => "MIXED_UNITS",
+            DuplicateLabel { .. } => "DUPLICATE_LABEL",
+            InvalidLabel { .. } => "INVALID_LABEL",
+            ConflictingLabels { .. } => "CONFLICTING_LABELS",
+            PrecisionOverflow { .. } => "PRECISION_OVERFLOW",
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match &self.kind {
+            InvalidGlass => write!(f, "expected something like '2/5'")?,
+            InvalidNumber => write!(f, "expected a positive number")?,
+            ZeroCapacity => write!(f, "capacity should be > 0")?,
+            OverflowingGlass { current, capacity } => write!(
+                f,
+                "{} does not fit into a capacity of {}",
+                current, capacity
+            )?,
+            NoGlass => write!(f, "expected at least one glass")?,
+            InvalidOperation => write!(f, "expected an empty, fill or pour operation")?,
+            UnknownGlass { name } => write!(f, "unknown glass '{}'", name)?,
+            SameGlass => write!(f, "cannot pour a glass into itself")?,
+            MixedUnits { first, second } => {
+                write!(f, "cannot mix '{}' and '{}' units", first, second)?
+            }
+            DuplicateLabel { name } => write!(f, "the name '{}' is used by several glasses", name)?,
+            InvalidLabel { name } => write!(
+                f,
+                "the name '{}' should use letters, digits, '_' or '-' and not be a number",
+                name
+            )?,
+            ConflictingLabels {
+                glass,
+                first,
+                second,
+            } => write!(f, "glass {} is named '{}' and '{}'", glass, first, second)?,
+            PrecisionOverflow { decimals } => write!(
+                f,
+                "volumes with {} decimals are too large to be represented",
+                decimals
+            )?,
+        }
+        match self.position {
+            Some(position) => write!(f, " at position {}", position),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub(crate) fn leading_spaces(s: &str) -> usize {
+    s.len() - s.trim_start().len()
+}
+
+fn parse_number<T: FromStr>(s: &str) -> Result<T, ParseError> {
+    let leading = leading_spaces(s);
+    s.trim()
+        .parse()
+        .map_err(|_| ParseError::new(InvalidNumber, leading))
+}
+
+// A volume as written, `mantissa / 10^decimals`
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Decimal {
+    mantissa: u64,
+    decimals: u32,
+}
+
+impl Decimal {
+    // The volume with more decimals, `None` when it does not fit into a `u32`
+    fn scale(self, decimals: u32) -> Option<u32> {
+        10u64
+            .checked_pow(decimals - self.decimals)
+            .and_then(|factor| self.mantissa.checked_mul(factor))
+            .and_then(|value| u32::try_from(value).ok())
+    }
+}
+
+// `2`, `2.5` or `0.75`, trailing zeros of the decimals are ignored
+fn parse_decimal(s: &str) -> Result<Decimal, ParseError> {
+    let leading = leading_spaces(s);
+    let text = s.trim();
+    let (integer, fraction) = match text.find('.') {
+        Some(dot) => (&text[..dot], Some(&text[dot + 1..])),
+        None => (text, None),
+    };
+    let digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    if !digits(integer) || !fraction.is_none_or(digits) {
+        return Err(ParseError::new(InvalidNumber, leading));
+    }
+    let fraction = fraction.unwrap_or_default().trim_end_matches('0');
+    let decimals = fraction.len() as u32;
+    let mantissa = format!("{}{}", integer, fraction)
+        .parse()
+        .map_err(|_| ParseError::new(PrecisionOverflow { decimals }, leading))?;
+
+    Ok(Decimal { mantissa, decimals })
+}
+
+// Plain states only have integer volumes, even `2.0` is rejected
+fn parse_integer(s: &str) -> Result<Decimal, ParseError> {
+    if s.contains('.') {
+        return Err(ParseError::new(InvalidNumber, leading_spaces(s)));
+    }
+    parse_decimal(s)
+}
+
+// The unit is the trailing letters, e.g. `5L`
+fn split_unit(s: &str) -> (&str, Option<String>) {
+    let trimmed = s.trim_end();
+    let number = trimmed.trim_end_matches(char::is_alphabetic);
+    match &trimmed[number.len()..] {
+        "" => (number, None),
+        unit => (number, Some(unit.to_string())),
+    }
+}
+
+// Volumes are kept as written, they are scaled once the decimals of the whole state are known
+struct Volumes {
+    current: Decimal,
+    capacity: Decimal,
+    unit: Option<String>,
+}
+
+impl Volumes {
+    fn decimals(&self) -> u32 {
+        self.current.decimals.max(self.capacity.decimals)
+    }
+
+    fn glass(&self, decimals: u32) -> Option<Glass> {
+        let current = self.current.scale(decimals)?;
+        let capacity = self.capacity.scale(decimals)?;
+        Some(Glass::new(current, capacity))
+    }
+}
+
+type NumberParser = fn(&str) -> Result<Decimal, ParseError>;
+
+fn parse_volumes(s: &str, number: NumberParser) -> Result<Volumes, ParseError> {
+    let slash = s
+        .find('/')
+        .ok_or_else(|| ParseError::new(InvalidGlass, 0))?;
+    let (current_text, current_unit) = split_unit(&s[..slash]);
+    let (capacity_text, capacity_unit) = split_unit(&s[slash + 1..]);
+    let current = number(current_text)?;
+    let capacity = number(capacity_text).map_err(|err| err.shift(slash + 1))?;
+    if capacity.mantissa == 0 {
+        return Err(ParseError::new(ZeroCapacity, slash + 1));
+    }
+    let unit = match (current_unit, capacity_unit) {
+        (Some(first), Some(second)) if first != second => {
+            return Err(ParseError::new(MixedUnits { first, second }, slash + 1));
+        }
+        (first, second) => second.or(first),
+    };
+    let volumes = Volumes {
+        current,
+        capacity,
+        unit,
+    };
+
+    let decimals = volumes.decimals();
+    let scaled = |volume: Decimal| {
+        volume
+            .scale(decimals)
+            .ok_or_else(|| ParseError::new(PrecisionOverflow { decimals }, 0))
+    };
+    if scaled(current)? > scaled(capacity)? {
+        let kind = OverflowingGlass {
+            current: current_text.trim().to_string(),
+            capacity: capacity_text.trim().to_string(),
+        };
+        return Err(ParseError::new(kind, 0));
+    }
+
+    Ok(volumes)
+}
+
+// Names must survive the plan notation, e.g. `pour big jug->cup`, and differ from glass indexes
+fn is_valid_label(label: &str) -> bool {
+    let allowed = |c: char| c.is_alphanumeric() || c == '_' || c == '-' || c == ' ';
+    label.chars().all(allowed) && !label.chars().all(|c| c.is_ascii_digit())
+}
+
+// `current/capacity` with an optional label and unit, e.g. `jug:0/5L`
+fn parse_glass(s: &str, number: NumberParser) -> Result<(Option<String>, Volumes), ParseError> {
+    let (label, volumes, offset) = match s.find(':') {
+        Some(colon) => match s[..colon].trim() {
+            "" => return Err(ParseError::new(InvalidGlass, 0)),
+            label if !is_valid_label(label) => {
+                let kind = InvalidLabel {
+                    name: label.to_string(),
+                };
+                return Err(ParseError::new(kind, leading_spaces(s)));
+            }
+            label => (Some(label.to_string()), &s[colon + 1..], colon + 1),
+        },
+        None => (None, s, 0),
+    };
+    let volumes = parse_volumes(volumes, number).map_err(|err| err.shift(offset))?;
+
+    Ok((label, volumes))
+}
+
+// Labels and units are ignored, decimal volumes are only read with `parse_labelled`
+impl FromStr for Glass {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (_, volumes) = parse_glass(s, parse_integer)?;
+        volumes
+            .glass(0)
+            .ok_or_else(|| ParseError::new(PrecisionOverflow { decimals: 0 }, 0))
+    }
+}
+
+// Glasses are separated by `,`, empty items are ignored, all glasses share the same unit.
+// Volumes are scaled with the largest number of decimals, e.g. `0/2.5, 0/0.75` is `0/250, 0/75`
+pub fn parse_labelled(s: &str) -> Result<(State, Labels), ParseError> {
+    parse_state(s, parse_decimal)
+}
+
+fn parse_state(s: &str, number: NumberParser) -> Result<(State, Labels), ParseError> {
+    let mut all: Vec<(usize, Volumes)> = vec![];
+    let mut names: Vec<Option<String>> = vec![];
+    let mut unit: Option<String> = None;
+    let mut offset = 0;
+    for item in s.split(',') {
+        let start = offset + leading_spaces(item);
+        offset += item.len() + 1;
+        if item.trim().is_empty() {
+            continue;
+        }
+        let (name, volumes) =
+            parse_glass(item.trim(), number).map_err(|err: ParseError| err.shift(start))?;
+        match (&unit, &volumes.unit) {
+            (Some(first), Some(second)) if first != second => {
+                let kind = MixedUnits {
+                    first: first.clone(),
+                    second: second.clone(),
+                };
+                return Err(ParseError::new(kind, start));
+            }
+            (None, Some(second)) => unit = Some(second.clone()),
+            _ => {}
+        }
+        all.push((start, volumes));
+        names.push(name);
+    }
+    if all.is_empty() {
+        return Err(ParseError::new(NoGlass, 0));
+    }
+
+    if let Some((index, name)) = name_clash(&names) {
+        let kind = DuplicateLabel {
+            name: name.to_string(),
+        };
+        return Err(ParseError::new(kind, all[index].0));
+    }
+
+    let decimals = all.iter().map(|(_, v)| v.decimals()).max().unwrap_or(0);
+    let glasses = all
+        .iter()
+        .map(|(start, volumes)| {
+            volumes
+                .glass(decimals)
+                .ok_or_else(|| ParseError::new(PrecisionOverflow { decimals }, *start))
+        })
+        .collect::<Result<Vec<Glass>, ParseError>>()?;
+    let labels = Labels::new(names, unit).with_decimals(decimals);
+
+    Ok((State::new(glasses), labels))
+}
+
+// Same volumes with more decimals
+pub(crate) fn rescale(state: &State, from: u32, to: u32) -> Result<State, ParseError> {
+    let overflow = || ParseError::without_position(PrecisionOverflow { decimals: to });
+    let factor = 10u32.checked_pow(to - from).ok_or_else(overflow)?;
+    let glasses = state
+        .iter()
+        .map(|glass| {
+            let current = glass.current.checked_mul(factor).ok_or_else(overflow)?;
+            let capacity = glass.capacity.checked_mul(factor).ok_or_else(overflow)?;
+            Ok(Glass::new(current, capacity))
+        })
+        .collect::<Result<Vec<Glass>, ParseError>>()?;
+
+    Ok(State::new(glasses))
+}
+
+impl FromStr for State {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_state(s, parse_integer).map(|(state, _)| state)
+    }
+}
+
+// Same syntax as the `Display`, e.g. `Fill(0)`, `Empty(1)` or `Pour(0->1)`
+impl FromStr for Operation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let leading = leading_spaces(s);
+        let text = s.trim();
+        let invalid = || ParseError::new(InvalidOperation, leading);
+        let open = text.find('(').ok_or_else(invalid)?;
+        if !text.ends_with(')') {
+            return Err(invalid());
+        }
+        let arguments = &text[open + 1..text.len() - 1];
+        let offset = leading + open + 1;
+        let glass = |s: &str, shift: usize| {
+            parse_number(s).map_err(|err: ParseError| err.shift(offset + shift))
+        };
+
+        match text[..open].trim_end() {
+            "Empty" => Ok(Operation::empty(glass(arguments, 0)?)),
+            "Fill" => Ok(Operation::fill(glass(arguments, 0)?)),
+            "Pour" => {
+                let arrow = arguments
+                    .find("->")
+                    .ok_or_else(|| ParseError::new(InvalidOperation, offset))?;
+                let from = glass(&arguments[..arrow], 0)?;
+                let to = glass(&arguments[arrow + 2..], arrow + 2)?;
+                if from == to {
+                    return Err(ParseError::new(SameGlass, offset));
+                }
+                Ok(Operation::pour(from, to))
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+// Operations are separated by spaces or `,`, e.g. `Fill(0) Pour(0->1) Empty(1)`
+pub fn parse_plan(s: &str) -> Result<Vec<Operation>, ParseError> {
+    let separator = |c: char| c.is_whitespace() || c == ',';
+    let mut operations: Vec<Operation> = vec![];
+    let mut start = 0;
+    loop {
+        let rest = &s[start..];
+        start += rest.len() - rest.trim_start_matches(separator).len();
+        if start == s.len() {
+            break;
+        }
+        let end = match s[start..].find(')') {
+            Some(index) => start + index + 1,
+            None => return Err(ParseError::new(InvalidOperation, start)),
+        };
+        let operation = s[start..end]
+            .parse()
+            .map_err(|err: ParseError| err.shift(start))?;
+        operations.push(operation);
+        start = end;
+    }
+
+    Ok(operations)
+}
+
+// Can be parsed back with `parse_plan`
+pub fn format_plan(operations: &[Operation]) -> String {
+    let operations: Vec<String> = operations.iter().map(|op| op.to_string()).collect();
+    operations.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod glass {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn parse_glass() {
+            assert_eq!("4/7".parse(), Ok(Glass::new(4, 7)));
+        }
+
+        #[test]
+        fn invalid_glasses() {
+            let error = |s: &str| s.parse::<Glass>().unwrap_err();
+
+            assert_eq!(error("3"), ParseError::new(InvalidGlass, 0));
+            assert_eq!(error("a/5"), ParseError::new(InvalidNumber, 0));
+            assert_eq!(error("3/b"), ParseError::new(InvalidNumber, 2));
+            assert_eq!(error("1/2/3"), ParseError::new(InvalidNumber, 2));
+            assert_eq!(error("0/0"), ParseError::new(ZeroCapacity, 2));
+            assert_eq!(
+                error("11/10"),
+                ParseError::new(
+                    OverflowingGlass {
+                        current: "11".to_string(),
+                        capacity: "10".to_string()
+                    },
+                    0
+                )
+            );
+        }
+    }
+
+    mod state {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn parse_state() {
+            assert_eq!("4/7, 3/5, 0/2,".parse(), Ok(State::from("4/7, 3/5, 0/2")));
+        }
+
+        #[test]
+        fn position_in_state() {
+            let error = "4/7, 3/a, 0/2".parse::<State>().unwrap_err();
+
+            assert_eq!(error, ParseError::new(InvalidNumber, 7));
+            assert_eq!(error.code(), "INVALID_NUMBER");
+            assert_eq!(
+                error.to_string(),
+                "expected a positive number at position 7"
+            );
+        }
+
+        #[test]
+        fn no_glass() {
+            assert_eq!(" , ".parse::<State>(), Err(ParseError::new(NoGlass, 0)));
+        }
+    }
+
+    mod decimal {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn parse_decimal_volumes() {
+            let (state, labels) = parse_labelled("0/2.5, 0/0.75").unwrap();
+
+            assert_eq!(state, State::from("0/250, 0/75"));
+            assert_eq!(labels.decimals(), 2);
+            assert_eq!(labels.state(&state), "0/2.5, 0/0.75");
+        }
+
+        #[test]
+        fn invalid_decimals() {
+            let error = |s: &str| parse_labelled(s).unwrap_err();
+
+            assert_eq!(error("0/2."), ParseError::new(InvalidNumber, 2));
+            assert_eq!(error("0/.5"), ParseError::new(InvalidNumber, 2));
+            assert_eq!(error("0/0.00"), ParseError::new(ZeroCapacity, 2));
+            assert_eq!(
+                error("2.5/2"),
+                ParseError::new(
+                    OverflowingGlass {
+                        current: "2.5".to_string(),
+                        capacity: "2".to_string()
+                    },
+                    0
+                )
+            );
+        }
+
+        #[test]
+        fn plain_states_reject_decimals() {
+            assert_eq!(
+                "1.5/3".parse::<Glass>(),
+                Err(ParseError::new(InvalidNumber, 0))
+            );
+            assert_eq!(
+                "jug:0/3.0".parse::<Glass>(),
+                Err(ParseError::new(InvalidNumber, 6))
+            );
+            assert_eq!(
+                "0/5, 0/2.5".parse::<State>(),
+                Err(ParseError::new(InvalidNumber, 7))
+            );
+        }
+
+        #[test]
+        fn precision_overflow() {
+            let error = |s: &str| parse_labelled(s).unwrap_err();
+
+            assert_eq!(
+                error("0/5000000000"),
+                ParseError::new(PrecisionOverflow { decimals: 0 }, 0)
+            );
+            assert_eq!(
+                error("0/0.000000000000000000001"),
+                ParseError::new(PrecisionOverflow { decimals: 21 }, 0)
+            );
+            assert_eq!(
+                error("0/5000000, 0/0.001").to_string(),
+                "volumes with 3 decimals are too large to be represented at position 0"
+            );
+        }
+    }
+
+    mod labelled {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn parse_labels_and_unit() {
+            let (state, labels) = parse_labelled("jug:0/5L, cup: 1L/3L").unwrap();
+
+            assert_eq!(state, State::from("0/5, 1/3"));
+            assert_eq!(labels.name(0), Some("jug"));
+            assert_eq!(labels.name(1), Some("cup"));
+            assert_eq!(labels.unit(), Some("L"));
+            assert_eq!(labels.state(&state), "jug:0/5L, cup:1/3L");
+        }
+
+        #[test]
+        fn partial_labels() {
+            let (_, labels) = parse_labelled("0/5, cup:0/3").unwrap();
+            assert_eq!(labels.names(), &[None, Some("cup".to_string())]);
+            assert_eq!(labels.unit(), None);
+
+            let (_, labels) = parse_labelled("0/5mL, 0/3").unwrap();
+            assert_eq!(labels.names(), &[] as &[Option<String>]);
+            assert_eq!(labels.unit(), Some("mL"));
+        }
+
+        #[test]
+        fn invalid_labels() {
+            let error = |s: &str| parse_labelled(s).unwrap_err();
+
+            assert_eq!(error(":0/5"), ParseError::new(InvalidGlass, 0));
+            assert_eq!(error("jug:0/x"), ParseError::new(InvalidNumber, 6));
+            assert_eq!(
+                error("jug:0/5, jug:0/3"),
+                ParseError::new(
+                    DuplicateLabel {
+                        name: "jug".to_string()
+                    },
+                    9
+                )
+            );
+            assert_eq!(
+                error("jug:0/5, JUG:0/3"),
+                ParseError::new(
+                    DuplicateLabel {
+                        name: "JUG".to_string()
+                    },
+                    9
+                )
+            );
+            assert_eq!(
+                error("0/5L, 0/3mL").to_string(),
+                "cannot mix 'L' and 'mL' units at position 6"
+            );
+            assert_eq!(error("1cL/5L").code(), "MIXED_UNITS");
+        }
+
+        #[test]
+        fn names_are_not_letters_of_unnamed_glasses() {
+            let error = |s: &str| parse_labelled(s).unwrap_err();
+            let duplicate = |name: &str, position| {
+                let kind = DuplicateLabel {
+                    name: name.to_string(),
+                };
+                ParseError::new(kind, position)
+            };
+
+            assert_eq!(error("0/5, a:0/3"), duplicate("a", 5));
+            assert_eq!(error("B:0/5, 0/3"), duplicate("B", 0));
+            assert!(parse_labelled("jug:0/5, a:0/3").is_ok());
+        }
+
+        #[test]
+        fn names_are_not_indexes_nor_operators() {
+            let error = |s: &str| parse_labelled(s).unwrap_err();
+
+            assert_eq!(
+                error("0/5, 1:0/3"),
+                ParseError::new(
+                    InvalidLabel {
+                        name: "1".to_string()
+                    },
+                    5
+                )
+            );
+            assert_eq!(error("a->b:0/5").code(), "INVALID_LABEL");
+            assert_eq!(error("a;b:0/5").code(), "INVALID_LABEL");
+            assert_eq!(error("a\"b:0/5").code(), "INVALID_LABEL");
+            assert!(parse_labelled("big jug:0/5, jug-1:0/3, 1st:0/2").is_ok());
+        }
+    }
+
+    mod operation {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn parse_operations() {
+            assert_eq!("Empty(2)".parse(), Ok(Operation::empty(2)));
+            assert_eq!(" Fill( 1 ) ".parse(), Ok(Operation::fill(1)));
+            assert_eq!("Pour(0 -> 1)".parse(), Ok(Operation::pour(0, 1)));
+        }
+
+        #[test]
+        fn display_round_trip() {
+            for operation in Operation::all(3) {
+                assert_eq!(operation.to_string().parse(), Ok(operation));
+            }
+        }
+
+        #[test]
+        fn invalid_operations() {
+            let error = |s: &str| s.parse::<Operation>().unwrap_err();
+
+            assert_eq!(error("Drink(0)"), ParseError::new(InvalidOperation, 0));
+            assert_eq!(error("Fill 0"), ParseError::new(InvalidOperation, 0));
+            assert_eq!(error("Fill(a)"), ParseError::new(InvalidNumber, 5));
+            assert_eq!(error("Pour(0, 1)"), ParseError::new(InvalidOperation, 5));
+            assert_eq!(error("Pour(0->x)"), ParseError::new(InvalidNumber, 8));
+            assert_eq!(error("Pour(1->1)"), ParseError::new(SameGlass, 5));
+        }
+
+        #[test]
+        fn parse_a_plan() {
+            let expected = vec![
+                Operation::fill(0),
+                Operation::pour(0, 1),
+                Operation::empty(1),
+            ];
+
+            assert_eq!(
+                parse_plan("Fill(0) Pour(0->1) Empty(1)"),
+                Ok(expected.clone())
+            );
+            assert_eq!(
+                parse_plan(" Fill(0),\n Pour(0 -> 1), Empty(1) "),
+                Ok(expected.clone())
+            );
+            assert_eq!(format_plan(&expected), "Fill(0) Pour(0->1) Empty(1)");
+            assert_eq!(parse_plan(""), Ok(vec![]));
+        }
+
+        #[test]
+        fn position_in_plan() {
+            let error = parse_plan("Fill(0) Pour(0->z) Empty(1)").unwrap_err();
+
+            assert_eq!(error, ParseError::new(InvalidNumber, 16));
+            assert_eq!(
+                parse_plan("Fill(0) Empty"),
+                Err(ParseError::new(InvalidOperation, 8))
+            );
+        }
+    }
+}
@@ -0,0 +1,345 @@
+use std::fmt::{Display, Error, Formatter};
+use std::slice::Iter;
+
+use crate::labels::Labels;
+use crate::operations::Operation;
+use crate::operations::Operation::{Empty, Fill, Pour};
+use crate::plan::PlanError::{NoOp, SameGlass, TargetNotReached, UnknownGlass};
+use crate::problem::Problem;
+use crate::state::State;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanError {
+    UnknownGlass { index: usize, operation: Operation },
+    SameGlass { index: usize, operation: Operation },
+    NoOp { index: usize, operation: Operation },
+    TargetNotReached { state: State },
+}
+
+impl PlanError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            UnknownGlass { .. } => "UNKNOWN_GLASS",
+            SameGlass { .. } => "SAME_GLASS",
+            NoOp { .. } => "NO_OP",
+            TargetNotReached { .. } => "TARGET_NOT_REACHED",
+        }
+    }
+
+    // The message with the glass names, unit and decimals of the problem
+    pub fn describe(&self, labels: &Labels) -> String {
+        match self {
+            UnknownGlass { index, operation } => format!(
+                "step {}: {} uses an unknown glass",
+                index,
+                labels.operation(operation)
+            ),
+            SameGlass { index, operation } => format!(
+                "step {}: {} pours a glass into itself",
+                index,
+                labels.operation(operation)
+            ),
+            NoOp { index, operation } => format!(
+                "step {}: {} changes nothing",
+                index,
+                labels.operation(operation)
+            ),
+            TargetNotReached { state } => format!("the plan ends with {}", labels.state(state)),
+        }
+    }
+}
+
+impl Display for PlanError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{}", self.describe(&Labels::default()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub before: State,
+    pub operation: Operation,
+    pub after: State,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace {
+    start: State,
+    steps: Vec<Step>,
+    // from the problem, to display the steps
+    labels: Labels,
+}
+
+impl Trace {
+    pub fn start(&self) -> &State {
+        &self.start
+    }
+
+    pub fn labels(&self) -> &Labels {
+        &self.labels
+    }
+
+    pub fn end(&self) -> &State {
+        self.steps.last().map_or(&self.start, |step| &step.after)
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<'_, Step> {
+        self.steps.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Trace {
+    type Item = &'a Step;
+    type IntoIter = Iter<'a, Step>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.steps.iter()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plan {
+    problem: Problem,
+    operations: Vec<Operation>,
+}
+
+impl Plan {
+    pub fn new(problem: Problem, operations: Vec<Operation>) -> Self {
+        Self {
+            problem,
+            operations,
+        }
+    }
+
+    pub fn problem(&self) -> &Problem {
+        &self.problem
+    }
+
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    // Replay all operations, the first illegal or useless step is reported
+    pub fn validate(&self) -> Result<Trace, PlanError> {
+        let mut steps: Vec<Step> = vec![];
+        let mut state = self.problem.from.clone();
+        for (index, &operation) in self.operations.iter().enumerate() {
+            check_operation(&state, index, operation)?;
+            let next = state.apply(operation);
+            if next == state {
+                return Err(NoOp { index, operation });
+            }
+            steps.push(Step {
+                before: state,
+                operation,
+                after: next.clone(),
+            });
+            state = next;
+        }
+
+        if state != self.problem.to {
+            return Err(TargetNotReached { state });
+        }
+
+        Ok(Trace {
+            start: self.problem.from.clone(),
+            steps,
+            labels: self.problem.labels.clone(),
+        })
+    }
+}
+
+pub(crate) fn check_operation(
+    state: &State,
+    index: usize,
+    operation: Operation,
+) -> Result<(), PlanError> {
+    let size = state.len();
+    let (glasses, same) = match operation {
+        Empty { glass } | Fill { glass } => (vec![glass], false),
+        Pour { from, to } => (vec![from, to], from == to),
+    };
+    if glasses.iter().any(|&glass| glass >= size) {
+        return Err(UnknownGlass { index, operation });
+    }
+    if same {
+        return Err(SameGlass { index, operation });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem() -> Problem {
+        Problem::from(("0/5, 0/3", "2/5, 0/3"))
+    }
+
+    mod validate {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn valid_plan() {
+            let operations = vec![
+                Operation::fill(0),
+                Operation::pour(0, 1),
+                Operation::empty(1),
+            ];
+            let plan = Plan::new(problem(), operations.clone());
+
+            let trace = plan.validate().expect("Should be valid");
+
+            assert_eq!(trace.len(), 3);
+            assert_eq!(trace.end(), &State::from("2/5, 0/3"));
+            let replayed: Vec<Operation> = trace.iter().map(|step| step.operation).collect();
+            assert_eq!(replayed, operations);
+        }
+
+        #[test]
+        fn steps_are_chained() {
+            let operations = vec![
+                Operation::fill(0),
+                Operation::pour(0, 1),
+                Operation::empty(1),
+            ];
+            let trace = Plan::new(problem(), operations).validate().unwrap();
+
+            let mut state = trace.start().clone();
+            for step in &trace {
+                assert_eq!(step.before, state);
+                assert_eq!(step.after, step.before.apply(step.operation));
+                state = step.after.clone();
+            }
+        }
+
+        #[test]
+        fn empty_plan_for_solved_problem() {
+            let problem = Problem::from(("1/5, 0/3", "1/5, 0/3"));
+
+            let trace = Plan::new(problem, vec![]).validate().unwrap();
+
+            assert!(trace.is_empty());
+        }
+
+        #[test]
+        fn unknown_glass() {
+            let operations = vec![Operation::fill(0), Operation::pour(0, 2)];
+
+            let result = Plan::new(problem(), operations).validate();
+
+            assert_eq!(
+                result,
+                Err(UnknownGlass {
+                    index: 1,
+                    operation: Operation::pour(0, 2)
+                })
+            );
+        }
+
+        #[test]
+        fn same_glass() {
+            let operation = Pour { from: 0, to: 0 };
+
+            let result = Plan::new(problem(), vec![operation]).validate();
+
+            assert_eq!(
+                result,
+                Err(SameGlass {
+                    index: 0,
+                    operation
+                })
+            );
+        }
+
+        #[test]
+        fn no_op() {
+            let operations = vec![Operation::fill(0), Operation::fill(0)];
+
+            let result = Plan::new(problem(), operations).validate();
+
+            assert_eq!(
+                result,
+                Err(NoOp {
+                    index: 1,
+                    operation: Operation::fill(0)
+                })
+            );
+        }
+
+        #[test]
+        fn target_not_reached() {
+            let operations = vec![Operation::fill(0)];
+
+            let result = Plan::new(problem(), operations).validate();
+
+            assert_eq!(
+                result,
+                Err(TargetNotReached {
+                    state: State::from("5/5, 0/3")
+                })
+            );
+        }
+    }
+
+    mod display {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn display_no_op() {
+            let error = NoOp {
+                index: 3,
+                operation: Operation::empty(1),
+            };
+
+            assert_eq!(error.code(), "NO_OP");
+            assert_eq!(error.to_string(), "step 3: Empty(1) changes nothing");
+        }
+
+        #[test]
+        fn describe_with_labels() {
+            let problem = Problem::parse("jug:0/2.5L, cup:0/0.75L", "1/2.5L, 0/0.75L").unwrap();
+            let plan = Plan::new(problem.clone(), vec![Operation::fill(0)]);
+
+            let error = plan.validate().unwrap_err();
+
+            assert_eq!(
+                error.describe(&problem.labels),
+                "the plan ends with jug:2.5/2.5L, cup:0/0.75L"
+            );
+            assert_eq!(
+                NoOp {
+                    index: 0,
+                    operation: Operation::pour(1, 0)
+                }
+                .describe(&problem.labels),
+                "step 0: Pour(cup->jug) changes nothing"
+            );
+        }
+    }
+}
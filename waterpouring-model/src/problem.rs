@@ -1,5 +1,13 @@
 use std::fmt::{Display, Error, Formatter};
 
+use crate::labels::{Labels, Volume};
+use crate::operations::GlassId;
+use crate::parse::{parse_labelled, rescale, ParseError};
+use crate::problem::Warning::{AlreadySolved, NoEmptyOrFullGlass, UnreachableVolume};
+use crate::solver::InvalidReason;
+use crate::solver::InvalidReason::{
+    CapacityMismatch, GlassCountMismatch, NotEnoughGlasses, TargetVolumeExceeded,
+};
 use crate::solver::SolverError;
 use crate::solver::SolverError::InvalidProblem;
 use crate::state::State;
@@ -8,58 +16,544 @@ use crate::state::State;
 pub struct Problem {
     pub from: State,
     pub to: State,
+    pub labels: Labels,
 }
 
 impl Problem {
     pub fn new(from: State, to: State) -> Self {
-        Self { from, to }
+        Self {
+            from,
+            to,
+            labels: Labels::default(),
+        }
+    }
+
+    pub fn with_labels(self, labels: Labels) -> Self {
+        Self { labels, ..self }
+    }
+
+    // Labels and unit can be given on any state, e.g. `jug:0/5L, cup:0/3L`
+    pub fn parse(from: &str, to: &str) -> Result<Self, ProblemParseError> {
+        let error = |input| move |error| ProblemParseError { input, error };
+        let from = parse_labelled(from).map_err(error(Some(Input::From)))?;
+        let to = parse_labelled(to).map_err(error(Some(Input::To)))?;
+
+        Self::labelled(from, to).map_err(error(None))
+    }
+
+    // Both states are scaled to the same number of decimals, e.g. `0/2.5` and `1/2.5` is `0/25` and `10/25`
+    fn labelled(from: (State, Labels), to: (State, Labels)) -> Result<Self, ParseError> {
+        let labels = from.1.merge(&to.1)?;
+        let decimals = labels.decimals();
+        let from = rescale(&from.0, from.1.decimals(), decimals)?;
+        let to = rescale(&to.0, to.1.decimals(), decimals)?;
+
+        Ok(Self::new(from, to).with_labels(labels))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Input {
+    From,
+    To,
+}
+
+impl Input {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Input::From => "from",
+            Input::To => "to",
+        }
+    }
+}
+
+// `input` is `None` when both states are valid but do not agree, e.g. on glass names
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProblemParseError {
+    pub input: Option<Input>,
+    pub error: ParseError,
+}
+
+impl ProblemParseError {
+    pub fn code(&self) -> &'static str {
+        self.error.code()
     }
 }
 
+impl Display for ProblemParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self.input {
+            Some(input) => write!(f, "Invalid '{}' state, {}", input.name(), self.error),
+            None => write!(f, "Invalid problem, {}", self.error),
+        }
+    }
+}
+
+impl std::error::Error for ProblemParseError {}
+
 impl From<(&str, &str)> for Problem {
     fn from(pair: (&str, &str)) -> Self {
-        Self {
-            from: State::from(pair.0),
-            to: State::from(pair.1),
-        }
+        Self::parse(pair.0, pair.1)
+            .unwrap_or_else(|err| panic!("Invalid problem '{:?}', {}", pair, err))
     }
 }
 
 impl Display for Problem {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
-        write!(f, "{} -> {}", self.from.clone(), self.to.clone())
+        write!(
+            f,
+            "{} -> {}",
+            self.labels.state(&self.from),
+            self.labels.state(&self.to)
+        )
     }
 }
 
-pub fn check_solvable(problem: &Problem) -> Result<&Problem, SolverError> {
-    let from = problem.from.clone();
-    let to = problem.to.clone();
+#[derive(Debug, Clone, PartialEq)]
+pub enum Warning {
+    AlreadySolved,
+    // every glass volume stays a multiple of the GCD of capacities and initial volumes
+    UnreachableVolume {
+        index: GlassId,
+        volume: Volume,
+        gcd: Volume,
+    },
+    // after any operation, at least one glass is empty or full
+    NoEmptyOrFullGlass,
+}
 
-    if from.glasses().len() < 2 {
-        return Err(InvalidProblem {
-            problem: problem.to_string(),
-            reason: "Should have at least two glasses".to_string(),
-        });
+impl Warning {
+    pub fn code(&self) -> &'static str {
+        match self {
+            AlreadySolved => "ALREADY_SOLVED",
+            UnreachableVolume { .. } => "UNREACHABLE_VOLUME",
+            NoEmptyOrFullGlass => "NO_EMPTY_OR_FULL_GLASS",
+        }
     }
+}
 
-    if from.glasses().len() != to.glasses().len() {
-        return Err(InvalidProblem {
-            problem: problem.to_string(),
-            reason: "Should have same number of glasses".to_string(),
-        });
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            AlreadySolved => write!(f, "The target is the initial state"),
+            UnreachableVolume { index, volume, gcd } => write!(
+                f,
+                "Glass {} cannot contain {}, volumes are multiple of {}",
+                index, volume, gcd
+            ),
+            NoEmptyOrFullGlass => write!(f, "The target should have an empty or a full glass"),
+        }
     }
+}
 
-    let has_invalid_size = from
-        .glasses()
-        .into_iter()
-        .zip(to.glasses().iter())
-        .any(|(g1, g2)| g1.capacity != g2.capacity);
-    if has_invalid_size {
-        return Err(InvalidProblem {
+#[derive(Debug, Clone, PartialEq)]
+pub enum Issue {
+    Error(InvalidReason),
+    Warning(Warning),
+}
+
+impl Issue {
+    pub fn is_error(&self) -> bool {
+        match self {
+            Issue::Error(_) => true,
+            Issue::Warning(_) => false,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Issue::Error(reason) => reason.code(),
+            Issue::Warning(warning) => warning.code(),
+        }
+    }
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Issue::Error(reason) => write!(f, "error: {}", reason),
+            Issue::Warning(warning) => write!(f, "warning: {}", warning),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Validation {
+    pub issues: Vec<Issue>,
+}
+
+impl Validation {
+    pub fn is_valid(&self) -> bool {
+        !self.issues.iter().any(Issue::is_error)
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &InvalidReason> {
+        self.issues.iter().filter_map(|issue| match issue {
+            Issue::Error(reason) => Some(reason),
+            Issue::Warning(_) => None,
+        })
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Warning> {
+        self.issues.iter().filter_map(|issue| match issue {
+            Issue::Error(_) => None,
+            Issue::Warning(warning) => Some(warning),
+        })
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// Collect all issues of the problem instead of stopping at the first one
+pub fn validate(problem: &Problem) -> Validation {
+    let (from, to) = (&problem.from, &problem.to);
+    let decimals = problem.labels.decimals();
+    let mut issues: Vec<Issue> = vec![];
+
+    if from.len() < 2 {
+        issues.push(Issue::Error(NotEnoughGlasses { count: from.len() }));
+    }
+
+    if from.len() != to.len() {
+        issues.push(Issue::Error(GlassCountMismatch {
+            from: from.len(),
+            to: to.len(),
+        }));
+    }
+
+    for (index, (g1, g2)) in from.iter().zip(to).enumerate() {
+        if g1.capacity != g2.capacity {
+            issues.push(Issue::Error(CapacityMismatch {
+                index,
+                from: Volume::new(g1.capacity.into(), decimals),
+                to: Volume::new(g2.capacity.into(), decimals),
+            }));
+        }
+    }
+
+    let volume = to.total_volume();
+    let capacity = from.total_capacity();
+    if volume > capacity {
+        issues.push(Issue::Error(TargetVolumeExceeded {
+            volume: Volume::new(volume, decimals),
+            capacity: Volume::new(capacity, decimals),
+        }));
+    }
+
+    if problem.from == problem.to {
+        issues.push(Issue::Warning(AlreadySolved));
+    } else {
+        let step = from
+            .iter()
+            .fold(0, |acc, g| gcd(gcd(acc, g.capacity), g.current));
+        for (index, g) in to.iter().enumerate() {
+            if step > 0 && g.current % step != 0 {
+                issues.push(Issue::Warning(UnreachableVolume {
+                    index,
+                    volume: Volume::new(g.current.into(), decimals),
+                    gcd: Volume::new(step.into(), decimals),
+                }));
+            }
+        }
+
+        if !to.iter().any(|g| g.is_empty() || g.is_full()) {
+            issues.push(Issue::Warning(NoEmptyOrFullGlass));
+        }
+    }
+
+    Validation { issues }
+}
+
+pub fn check_solvable(problem: &Problem) -> Result<&Problem, SolverError> {
+    match validate(problem).errors().next() {
+        Some(reason) => Err(InvalidProblem {
             problem: problem.to_string(),
-            reason: "Should have same capacity for all glasses".to_string(),
-        });
+            reason: reason.clone(),
+        }),
+        None => Ok(problem),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    mod parse {
+        use pretty_assertions::assert_eq;
+
+        use crate::operations::Operation;
+
+        use super::*;
+
+        #[test]
+        fn unlabelled_problem() {
+            let problem = Problem::from(("0/5, 0/3", "4/5, 0/3"));
+
+            assert_eq!(
+                problem,
+                Problem::new(State::from("0/5, 0/3"), State::from("4/5, 0/3"))
+            );
+            assert_eq!(problem.to_string(), "0/5, 0/3 -> 4/5, 0/3");
+        }
+
+        #[test]
+        fn labelled_problem() {
+            let problem = Problem::from(("jug:0/5L, cup:0/3L", "4/5, 0/3"));
+
+            assert_eq!(problem.from, State::from("0/5, 0/3"));
+            assert_eq!(problem.labels.name(1), Some("cup"));
+            assert_eq!(
+                problem.to_string(),
+                "jug:0/5L, cup:0/3L -> jug:4/5L, cup:0/3L"
+            );
+        }
+
+        #[test]
+        fn locate_errors() {
+            let error = Problem::parse("0/5, 0/3", "4/5, 0/x").unwrap_err();
+
+            assert_eq!(error.input, Some(Input::To));
+            assert_eq!(error.error.position, Some(7));
+            assert_eq!(
+                error.to_string(),
+                "Invalid 'to' state, expected a positive number at position 7"
+            );
+        }
+
+        #[test]
+        fn conflicting_labels() {
+            let error = Problem::parse("jug:0/5L", "bucket:4/5").unwrap_err();
+
+            assert_eq!(error.code(), "CONFLICTING_LABELS");
+            assert_eq!(error.input, None);
+            assert_eq!(error.error.position, None);
+            assert_eq!(
+                error.to_string(),
+                "Invalid problem, glass 0 is named 'jug' and 'bucket'"
+            );
+        }
+
+        #[test]
+        fn decimal_problem() {
+            let problem = Problem::from(("0/2.5L, 0/0.75L", "1/2.5, 0/0.75"));
+
+            assert_eq!(problem.from, State::from("0/250, 0/75"));
+            assert_eq!(problem.to, State::from("100/250, 0/75"));
+            assert_eq!(problem.to_string(), "0/2.5L, 0/0.75L -> 1/2.5L, 0/0.75L");
+        }
+
+        #[test]
+        fn precision_overflow() {
+            let error = Problem::parse("0/500000000", "0.5/1").unwrap_err();
+
+            assert_eq!(error.code(), "PRECISION_OVERFLOW");
+        }
+
+        #[test]
+        fn largest_decimal_volumes() {
+            let problem = Problem::from(("0/429496.7295, 0/3", "1/429496.7295, 0/3"));
+
+            let state = problem
+                .from
+                .apply(Operation::fill(0))
+                .apply(Operation::fill(1));
+
+            assert_eq!(problem.from, State::from("0/4294967295, 0/30000"));
+            assert!(validate(&problem).is_valid());
+            assert_eq!(state.total_volume(), 4_294_997_295);
+            assert_eq!(
+                state.apply(Operation::pour(1, 0)).to_string(),
+                "4294967295/4294967295, 30000/30000"
+            );
+        }
+    }
+
+    mod validate {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn valid_problem() {
+            let problem = Problem::from(("0/5, 0/3", "4/5, 0/3"));
+
+            let result = validate(&problem);
+
+            assert!(result.is_valid());
+            assert_eq!(result.issues, vec![]);
+        }
+
+        #[test]
+        fn collect_all_errors() {
+            let problem = Problem::from(("0/5", "5/6, 3/3"));
+
+            let result = validate(&problem);
+
+            assert!(!result.is_valid());
+            assert_eq!(
+                result.errors().cloned().collect::<Vec<_>>(),
+                vec![
+                    NotEnoughGlasses { count: 1 },
+                    GlassCountMismatch { from: 1, to: 2 },
+                    CapacityMismatch {
+                        index: 0,
+                        from: 5.into(),
+                        to: 6.into()
+                    },
+                    TargetVolumeExceeded {
+                        volume: 8.into(),
+                        capacity: 5.into()
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn large_volumes() {
+            let problem = Problem::from((
+                "0/3000000000, 0/3000000000",
+                "3000000000/3000000000, 3000000000/3000000000",
+            ));
+
+            let result = validate(&problem);
+
+            assert!(result.is_valid());
+        }
+
+        #[test]
+        fn large_volume_exceeded() {
+            let problem = Problem::from(("0/4000000000", "4000000000/4000000000, 1/1"));
+
+            let result = validate(&problem);
+
+            assert_eq!(
+                result.errors().last(),
+                Some(&TargetVolumeExceeded {
+                    volume: Volume::new(4_000_000_001, 0),
+                    capacity: 4_000_000_000.into()
+                })
+            );
+        }
+
+        #[test]
+        fn all_capacity_mismatches() {
+            let problem = Problem::from(("0/5, 0/3, 0/2", "0/4, 0/3, 0/1"));
+
+            let result = validate(&problem);
+
+            let codes: Vec<&str> = result.issues.iter().map(Issue::code).collect();
+            assert_eq!(codes, vec!["CAPACITY_MISMATCH", "CAPACITY_MISMATCH"]);
+        }
+
+        #[test]
+        fn already_solved() {
+            let problem = Problem::from(("1/5, 0/3", "1/5, 0/3"));
+
+            let result = validate(&problem);
+
+            assert!(result.is_valid());
+            assert_eq!(result.issues, vec![Issue::Warning(AlreadySolved)]);
+        }
+
+        #[test]
+        fn unreachable_volume() {
+            let problem = Problem::from(("0/8, 0/4, 0/2", "1/8, 0/4, 0/2"));
+
+            let result = validate(&problem);
+
+            assert!(result.is_valid());
+            assert_eq!(
+                result.warnings().cloned().collect::<Vec<_>>(),
+                vec![UnreachableVolume {
+                    index: 0,
+                    volume: 1.into(),
+                    gcd: 2.into()
+                }]
+            );
+        }
+
+        #[test]
+        fn decimal_volumes() {
+            let mismatch = Problem::parse("0/2.5, 0/1", "0/2.5, 0/1.5").unwrap();
+            let unreachable = Problem::parse("0/2.5, 0/1.5", "0.3/2.5, 0/1.5").unwrap();
+
+            let issues: Vec<String> = validate(&mismatch)
+                .issues
+                .iter()
+                .chain(&validate(&unreachable).issues)
+                .map(Issue::to_string)
+                .collect();
+
+            assert_eq!(
+                issues,
+                vec![
+                    "error: Should have same capacity for all glasses, glass 1 has 1 and 1.5",
+                    "warning: Glass 0 cannot contain 0.3, volumes are multiple of 0.5",
+                ]
+            );
+        }
+
+        #[test]
+        fn no_empty_or_full_glass() {
+            let problem = Problem::from(("0/5, 0/3", "2/5, 1/3"));
+
+            let result = validate(&problem);
+
+            assert_eq!(result.issues, vec![Issue::Warning(NoEmptyOrFullGlass)]);
+        }
     }
 
-    Ok(problem)
+    mod check_solvable {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn report_first_error() {
+            let problem = Problem::from(("0/5, 0/3, 0/2", "0/4, 0/3, 0/1"));
+
+            let result = check_solvable(&problem);
+
+            assert_eq!(
+                result,
+                Err(InvalidProblem {
+                    problem: problem.to_string(),
+                    reason: CapacityMismatch {
+                        index: 0,
+                        from: 5.into(),
+                        to: 4.into()
+                    }
+                })
+            );
+        }
+
+        #[test]
+        fn accept_warnings() {
+            let problem = Problem::from(("0/5, 0/3", "2/5, 1/3"));
+
+            assert_eq!(check_solvable(&problem), Ok(&problem));
+        }
+    }
+
+    #[test]
+    fn display_issue() {
+        let issue = Issue::Warning(UnreachableVolume {
+            index: 1,
+            volume: 3.into(),
+            gcd: 2.into(),
+        });
+
+        assert_eq!(
+            issue.to_string(),
+            "warning: Glass 1 cannot contain 3, volumes are multiple of 2"
+        );
+    }
 }
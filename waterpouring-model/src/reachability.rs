@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use crate::state::State;
+
+// Breadth first exploration, give the minimal number of operations to reach each state
+pub fn distances(from: &State) -> HashMap<State, usize> {
+    let mut result: HashMap<State, usize> = HashMap::new();
+    result.insert(from.clone(), 0);
+    let mut states = vec![from.clone()];
+    let mut depth = 0;
+
+    while !states.is_empty() {
+        depth += 1;
+        let mut next_states = vec![];
+        for state in states {
+            for op in state.available_operations() {
+                let next = state.apply(op);
+                if !result.contains_key(&next) {
+                    result.insert(next.clone(), depth);
+                    next_states.push(next);
+                }
+            }
+        }
+        states = next_states;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn start_at_zero() {
+        let from = State::from("0/5, 0/3");
+
+        let result = distances(&from);
+
+        assert_eq!(result.get(&from), Some(&0));
+    }
+
+    #[test]
+    fn classic_problem() {
+        let from = State::from("0/5, 0/3");
+
+        let result = distances(&from);
+
+        assert_eq!(result.get(&State::from("4/5, 0/3")), Some(&7));
+        assert_eq!(result.get(&State::from("1/5, 1/3")), None);
+    }
+
+    #[test]
+    fn reachable_states() {
+        let from = State::from("0/2, 0/1");
+
+        let result = distances(&from);
+
+        // all states with at least one glass empty or full
+        assert_eq!(result.len(), 6);
+    }
+}
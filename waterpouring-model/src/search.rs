@@ -0,0 +1,245 @@
+use std::collections::HashSet;
+use std::mem;
+
+use crate::operations::Operation;
+use crate::problem::{check_solvable, Problem};
+use crate::solver::Evidence::Exhausted;
+use crate::solver::SolverError::UnsolvableProblem;
+use crate::solver::{Limits, Solution, SolverError, StateWithHistory, Stats};
+use crate::state::State;
+use crate::symmetry::Symmetry;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    pub depth: usize,
+    pub visited: usize,
+    // states left to expand, in the current and the next level
+    pub frontier: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchStatus {
+    Running(Progress),
+    Done(Result<Solution, SolverError>),
+}
+
+// A breadth first search that can be paused, each call to `step` expands a bounded number of states
+#[derive(Debug)]
+pub struct Search {
+    problem: Problem,
+    limits: Limits,
+    symmetry: Symmetry,
+    visited: HashSet<State>,
+    current: StateWithHistory,
+    next: StateWithHistory,
+    depth: usize,
+    level_visited: usize,
+    outcome: Option<Result<Solution, SolverError>>,
+}
+
+impl Search {
+    pub fn new(problem: Problem, limits: Limits) -> Self {
+        let symmetry = limits.symmetry(&problem);
+        let mut visited = HashSet::new();
+        visited.insert(symmetry.canonical(&problem.from));
+        let outcome = check_solvable(&problem).err().map(Err);
+
+        Self {
+            current: vec![(problem.from.clone(), vec![])],
+            next: vec![],
+            problem,
+            limits,
+            symmetry,
+            visited,
+            depth: 0,
+            level_visited: 1,
+            outcome,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    pub fn progress(&self) -> Progress {
+        Progress {
+            depth: self.depth,
+            visited: self.visited.len(),
+            frontier: self.current.len() + self.next.len(),
+        }
+    }
+
+    // Expand up to `budget` states, the target is checked when a state is expanded
+    pub fn step(&mut self, budget: usize) -> SearchStatus {
+        let mut remaining = budget;
+        while self.outcome.is_none() && remaining > 0 {
+            match self.current.pop() {
+                Some((state, history)) => {
+                    remaining -= 1;
+                    self.expand(state, history);
+                }
+                None => self.next_level(),
+            }
+        }
+
+        match &self.outcome {
+            Some(outcome) => SearchStatus::Done(outcome.clone()),
+            None => SearchStatus::Running(self.progress()),
+        }
+    }
+
+    // Run the search to the end
+    pub fn finish(&mut self) -> Result<Solution, SolverError> {
+        loop {
+            if let SearchStatus::Done(outcome) = self.step(usize::MAX) {
+                return outcome;
+            }
+        }
+    }
+
+    fn stats(&self) -> Stats {
+        Stats {
+            depth: self.depth,
+            visited: self.level_visited,
+        }
+    }
+
+    fn expand(&mut self, state: State, history: Vec<Operation>) {
+        if state == self.problem.to {
+            self.outcome = Some(Ok(Solution {
+                operations: history,
+                stats: self.stats(),
+            }));
+            return;
+        }
+        for op in state.available_operations() {
+            let new_state = state.apply(op);
+            if self.visited.insert(self.symmetry.canonical(&new_state)) {
+                let mut new_history = history.clone();
+                new_history.push(op);
+                self.next.push((new_state, new_history));
+            }
+        }
+    }
+
+    fn next_level(&mut self) {
+        if let Err(error) = self.limits.check(&self.problem, self.stats()) {
+            self.outcome = Some(Err(error));
+        } else if self.next.is_empty() {
+            self.outcome = Some(Err(UnsolvableProblem {
+                problem: self.problem.to_string(),
+                evidence: Some(Exhausted {
+                    visited: self.visited.len(),
+                }),
+            }));
+        } else {
+            // popped from the end, so reverse to keep the solvers order
+            self.current = mem::take(&mut self.next);
+            self.current.reverse();
+            self.depth += 1;
+            self.level_visited = self.visited.len();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::plan::Plan;
+    use crate::solver::InvalidReason::GlassCountMismatch;
+    use crate::solver::SolverError::{InvalidProblem, LimitExceeded};
+
+    use super::*;
+
+    #[test]
+    fn solve_step_by_step() {
+        let problem = Problem::from(("0/5, 0/3", "4/5, 0/3"));
+        let mut search = Search::new(problem.clone(), Limits::default());
+
+        let mut steps = 0;
+        let solution = loop {
+            steps += 1;
+            match search.step(2) {
+                SearchStatus::Running(progress) => assert!(progress.visited > 0),
+                SearchStatus::Done(outcome) => break outcome.expect("Should found a solution"),
+            }
+        };
+
+        assert!(steps > 1);
+        assert_eq!(solution.stats.depth, 7);
+        let trace = Plan::new(problem, solution.operations).validate().unwrap();
+        assert_eq!(trace.len(), 7);
+    }
+
+    #[test]
+    fn report_progress() {
+        let problem = Problem::from(("0/5, 0/3", "4/5, 0/3"));
+        let mut search = Search::new(problem, Limits::default());
+
+        let status = search.step(1);
+
+        assert_eq!(
+            status,
+            SearchStatus::Running(Progress {
+                depth: 0,
+                visited: 3,
+                frontier: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn done_is_stable() {
+        let problem = Problem::from(("1/5, 0/3", "1/5, 0/3"));
+        let mut search = Search::new(problem, Limits::default());
+
+        let first = search.step(10);
+
+        assert!(search.is_done());
+        assert_eq!(search.step(10), first);
+    }
+
+    #[test]
+    fn invalid_problem() {
+        let problem = Problem::from(("0/8, 0/4, 0/2", "0/4, 0/2"));
+
+        let result = Search::new(problem.clone(), Limits::default()).finish();
+
+        assert_eq!(
+            result,
+            Err(InvalidProblem {
+                problem: problem.to_string(),
+                reason: GlassCountMismatch { from: 3, to: 2 },
+            })
+        );
+    }
+
+    #[test]
+    fn no_solution() {
+        let problem = Problem::from(("0/8, 0/4, 0/2", "1/8, 0/4, 0/2"));
+
+        let result = Search::new(problem.clone(), Limits::default()).finish();
+
+        assert_eq!(
+            result,
+            Err(UnsolvableProblem {
+                problem: problem.to_string(),
+                evidence: Some(Exhausted { visited: 30 }),
+            })
+        );
+    }
+
+    #[test]
+    fn stop_at_max_depth() {
+        let problem = Problem::from(("0/5, 0/3", "4/5, 0/3"));
+        let limits = Limits {
+            max_depth: Some(6),
+            ..Limits::default()
+        };
+
+        let result = Search::new(problem, limits).finish();
+
+        assert!(matches!(result, Err(LimitExceeded { .. })));
+    }
+}
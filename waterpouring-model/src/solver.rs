@@ -1,21 +1,220 @@
-use crate::operations::Operation;
-use crate::problem::Problem;
-use crate::solver::SolverError::{InvalidProblem, UnsolvableProblem};
+use std::fmt::{Display, Error, Formatter};
+use std::io;
+use std::io::IsTerminal;
+use std::time::Duration;
+
+use crate::ascii::{animate, AnimationOptions};
+
+use crate::labels::Volume;
+use crate::operations::{GlassId, Operation};
+use crate::plan::Plan;
+use crate::problem::{validate, Problem};
+use crate::solver::Evidence::Exhausted;
+use crate::solver::InvalidReason::{
+    CapacityMismatch, GlassCountMismatch, NotEnoughGlasses, TargetVolumeExceeded,
+};
+use crate::solver::Limit::{MaxDepth, MaxStates};
+use crate::solver::SolverError::{InvalidProblem, LimitExceeded, UnsolvableProblem};
 use crate::state::State;
+use crate::symmetry::Symmetry;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvalidReason {
+    NotEnoughGlasses {
+        count: usize,
+    },
+    GlassCountMismatch {
+        from: usize,
+        to: usize,
+    },
+    CapacityMismatch {
+        index: GlassId,
+        from: Volume,
+        to: Volume,
+    },
+    TargetVolumeExceeded {
+        volume: Volume,
+        capacity: Volume,
+    },
+}
+
+impl InvalidReason {
+    // Stable identifiers, safe to expose to other layers
+    pub fn code(&self) -> &'static str {
+        match self {
+            NotEnoughGlasses { .. } => "NOT_ENOUGH_GLASSES",
+            GlassCountMismatch { .. } => "GLASS_COUNT_MISMATCH",
+            CapacityMismatch { .. } => "CAPACITY_MISMATCH",
+            TargetVolumeExceeded { .. } => "TARGET_VOLUME_EXCEEDED",
+        }
+    }
+}
+
+impl Display for InvalidReason {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            NotEnoughGlasses { count } => {
+                write!(f, "Should have at least two glasses, got {}", count)
+            }
+            GlassCountMismatch { from, to } => write!(
+                f,
+                "Should have same number of glasses, got {} and {}",
+                from, to
+            ),
+            CapacityMismatch { index, from, to } => write!(
+                f,
+                "Should have same capacity for all glasses, glass {} has {} and {}",
+                index, from, to
+            ),
+            TargetVolumeExceeded { volume, capacity } => write!(
+                f,
+                "Should not exceed the total capacity, target has {} for {}",
+                volume, capacity
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Evidence {
+    // every reachable state has been visited without finding the target
+    Exhausted { visited: usize },
+}
+
+impl Display for Evidence {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            Exhausted { visited } => write!(f, "{} reachable states visited", visited),
+        }
+    }
+}
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Limit {
+    MaxDepth(usize),
+    MaxStates(usize),
+}
+
+impl Display for Limit {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            MaxDepth(depth) => write!(f, "no solution with at most {} operations", depth),
+            MaxStates(states) => write!(f, "more than {} states visited", states),
+        }
+    }
+}
+
+// Bounds of a search, no bound and symmetry reduction by default
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Limits {
+    pub max_depth: Option<usize>,
+    pub max_states: Option<usize>,
+    // also visit states that only swap glasses of the same capacity
+    pub symmetric_states: bool,
+}
+
+impl Limits {
+    pub fn symmetry(&self, problem: &Problem) -> Symmetry {
+        if self.symmetric_states {
+            Symmetry::none()
+        } else {
+            Symmetry::of_problem(problem)
+        }
+    }
+
+    // Called before expanding the states at `stats.depth`
+    pub fn check(&self, problem: &Problem, stats: Stats) -> Result<(), SolverError> {
+        let exceeded = match (self.max_depth, self.max_states) {
+            (Some(depth), _) if stats.depth >= depth => Some(MaxDepth(depth)),
+            (_, Some(states)) if stats.visited > states => Some(MaxStates(states)),
+            _ => None,
+        };
+        match exceeded {
+            Some(limit) => Err(LimitExceeded {
+                problem: problem.to_string(),
+                limit,
+                stats,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Stats {
+    // number of levels of the breadth first search
+    pub depth: usize,
+    pub visited: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    pub operations: Vec<Operation>,
+    pub stats: Stats,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum SolverError {
-    InvalidProblem { problem: String, reason: String },
-    UnsolvableProblem { problem: String },
+    InvalidProblem {
+        problem: String,
+        reason: InvalidReason,
+    },
+    UnsolvableProblem {
+        problem: String,
+        evidence: Option<Evidence>,
+    },
+    LimitExceeded {
+        problem: String,
+        limit: Limit,
+        stats: Stats,
+    },
+}
+
+impl SolverError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            InvalidProblem { reason, .. } => reason.code(),
+            UnsolvableProblem { .. } => "UNSOLVABLE",
+            LimitExceeded { .. } => "LIMIT_EXCEEDED",
+        }
+    }
+}
+
+impl Display for SolverError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            InvalidProblem { problem, reason } => {
+                write!(f, "Invalid problem {}: {}", problem, reason)
+            }
+            UnsolvableProblem {
+                problem,
+                evidence: Some(evidence),
+            } => write!(f, "No solution for {}, {}", problem, evidence),
+            UnsolvableProblem {
+                problem,
+                evidence: None,
+            } => write!(f, "No solution for {}", problem),
+            LimitExceeded { problem, limit, .. } => {
+                write!(f, "Search stopped for {}, {}", problem, limit)
+            }
+        }
+    }
 }
 
+impl std::error::Error for SolverError {}
+
 pub type SolverResult = Result<Vec<Operation>, SolverError>;
 
 // FIXME maybe of just `fn`
 //pub type ASolver = dyn Fn(State, State) -> SolverResult;
 
 pub trait Solver {
-    fn solve(&self, problem: Problem) -> SolverResult;
+    fn solve(&self, problem: Problem) -> SolverResult {
+        self.solve_with(problem, &Limits::default())
+            .map(|solution| solution.operations)
+    }
+
+    fn solve_with(&self, problem: Problem, limits: &Limits) -> Result<Solution, SolverError>;
 }
 
 pub type StateWithHistory = Vec<(State, Vec<Operation>)>;
@@ -23,32 +222,126 @@ pub type StateWithHistory = Vec<(State, Vec<Operation>)>;
 pub fn test_solver(input: &str, output: &str, solver: &dyn Solver) -> usize {
     let from = State::from(input);
     let to = State::from(output);
-    let problem = Problem { from, to };
+    let problem = Problem::new(from, to);
 
-    solver
+    let operations = solver
         .solve(problem.clone())
-        .map(|lst| lst.len())
-        .expect("Should found a solution")
+        .expect("Should found a solution");
+
+    Plan::new(problem, operations)
+        .validate()
+        .map(|trace| trace.len())
+        .expect("Should be a valid plan")
 }
 
 pub fn solve<S>(solver: &S, from: &State, to: State)
 where
-    S: Solver,
+    S: Solver + ?Sized,
+{
+    solve_animated(solver, Problem::new(from.clone(), to), None)
+}
+
+// Glasses are drawn only when the output is a terminal
+pub fn solve_animated<S>(solver: &S, problem: Problem, delay: Option<Duration>)
+where
+    S: Solver + ?Sized,
 {
-    println!("Solve {} -> {}", from, to);
-    let problem = Problem::new(from.clone(), to);
+    println!("Solve {}", problem);
+    for warning in validate(&problem).warnings() {
+        println!("Warning: {}", warning);
+    }
     let result = solver.solve(problem.clone());
     match result {
         Err(InvalidProblem { reason, .. }) => println!("Cannot solve because {}", reason),
         Err(UnsolvableProblem { .. }) => println!("No solution found!"),
-        Ok(moves) => {
-            println!("A solution found");
-            let mut state = from.clone();
-            for m in moves {
-                let next = state.apply(m);
-                println!("{} with {} give {}", state, m, next);
-                state = next;
+        Err(error @ LimitExceeded { .. }) => println!("{}", error),
+        Ok(moves) => match Plan::new(problem, moves).validate() {
+            Err(error) => println!("Invalid solution, {}", error),
+            Ok(trace) => {
+                println!("A solution found");
+                let mut out = io::stdout();
+                let options = AnimationOptions {
+                    delay,
+                    tty: out.is_terminal(),
+                };
+                animate(&mut out, &trace, &options).expect("Cannot write the solution");
             }
-        }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn invalid_problem_code() {
+        let error = InvalidProblem {
+            problem: "0/5 -> 0/5".to_string(),
+            reason: NotEnoughGlasses { count: 1 },
+        };
+
+        assert_eq!(error.code(), "NOT_ENOUGH_GLASSES");
+    }
+
+    #[test]
+    fn display_invalid_problem() {
+        let error = InvalidProblem {
+            problem: "0/5, 0/3 -> 0/5, 0/4".to_string(),
+            reason: CapacityMismatch {
+                index: 1,
+                from: 3.into(),
+                to: 4.into(),
+            },
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid problem 0/5, 0/3 -> 0/5, 0/4: \
+             Should have same capacity for all glasses, glass 1 has 3 and 4"
+        );
+    }
+
+    #[test]
+    fn check_limits() {
+        let problem = Problem::from(("0/5, 0/3", "4/5, 0/3"));
+        let limits = Limits {
+            max_depth: Some(3),
+            max_states: Some(10),
+            ..Limits::default()
+        };
+        let stats = |depth, visited| Stats { depth, visited };
+
+        assert_eq!(limits.check(&problem, stats(2, 10)), Ok(()));
+        assert_eq!(
+            limits.check(&problem, stats(3, 4)),
+            Err(LimitExceeded {
+                problem: problem.to_string(),
+                limit: MaxDepth(3),
+                stats: stats(3, 4),
+            })
+        );
+        let error = limits.check(&problem, stats(2, 11)).unwrap_err();
+        assert_eq!(error.code(), "LIMIT_EXCEEDED");
+        assert_eq!(
+            error.to_string(),
+            "Search stopped for 0/5, 0/3 -> 4/5, 0/3, more than 10 states visited"
+        );
+    }
+
+    #[test]
+    fn display_unsolvable_problem() {
+        let error = UnsolvableProblem {
+            problem: "0/4, 0/2 -> 1/4, 0/2".to_string(),
+            evidence: Some(Exhausted { visited: 6 }),
+        };
+
+        assert_eq!(error.code(), "UNSOLVABLE");
+        assert_eq!(
+            error.to_string(),
+            "No solution for 0/4, 0/2 -> 1/4, 0/2, 6 reachable states visited"
+        );
     }
 }
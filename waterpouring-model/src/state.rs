@@ -1,9 +1,11 @@
 use std::fmt::{Display, Error, Formatter};
 use std::hash::Hash;
+use std::ops::Index;
+use std::slice::Iter;
 
 use crate::glass::Glass;
-use crate::operations::Operation;
 use crate::operations::Operation::{Empty, Fill, Pour};
+use crate::operations::{GlassId, Operation};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct State {
@@ -16,10 +18,40 @@ impl State {
         Self { glasses }
     }
 
+    pub fn builder() -> StateBuilder {
+        StateBuilder::default()
+    }
+
     pub fn glasses(&self) -> Vec<Glass> {
         self.glasses.clone()
     }
 
+    pub fn iter(&self) -> Iter<'_, Glass> {
+        self.glasses.iter()
+    }
+
+    pub fn get(&self, glass: GlassId) -> Option<&Glass> {
+        self.glasses.get(glass)
+    }
+
+    pub fn len(&self) -> usize {
+        self.glasses.len()
+    }
+
+    // Always false for a state created with `new`, provided for consistency with `len`
+    pub fn is_empty(&self) -> bool {
+        self.glasses.is_empty()
+    }
+
+    // Totals are `u64`, a sum of `u32` volumes may not fit in a `u32`
+    pub fn total_volume(&self) -> u64 {
+        self.glasses.iter().map(|g| u64::from(g.current)).sum()
+    }
+
+    pub fn total_capacity(&self) -> u64 {
+        self.glasses.iter().map(|g| u64::from(g.capacity)).sum()
+    }
+
     pub fn apply(&self, operation: Operation) -> Self {
         let next_glasses = self
             .glasses
@@ -82,33 +114,64 @@ impl State {
     }
 }
 
+impl Index<GlassId> for State {
+    type Output = Glass;
+
+    fn index(&self, glass: GlassId) -> &Self::Output {
+        &self.glasses[glass]
+    }
+}
+
+impl<'a> IntoIterator for &'a State {
+    type Item = &'a Glass;
+    type IntoIter = Iter<'a, Glass>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.glasses.iter()
+    }
+}
+
+// Build a state glass by glass, e.g. `State::builder().full(5).empty(3).build()`
+#[derive(Debug, Clone, Default)]
+pub struct StateBuilder {
+    glasses: Vec<Glass>,
+}
+
+impl StateBuilder {
+    pub fn glass(mut self, current: u32, capacity: u32) -> Self {
+        self.glasses.push(Glass::new(current, capacity));
+        self
+    }
+
+    pub fn empty(self, capacity: u32) -> Self {
+        self.glass(0, capacity)
+    }
+
+    pub fn full(self, capacity: u32) -> Self {
+        self.glass(capacity, capacity)
+    }
+
+    pub fn build(self) -> State {
+        State::new(self.glasses)
+    }
+}
+
 impl From<&str> for State {
     fn from(s: &str) -> Self {
-        let glasses: Vec<Glass> = s
-            .split(',')
-            .map(str::trim)
-            .filter_map(|s| {
-                if s.is_empty() {
-                    None
-                } else {
-                    Some(Glass::from(s))
-                }
-            })
-            .collect();
-        Self::new(glasses)
+        s.parse()
+            .unwrap_or_else(|err| panic!("Invalid string '{}', {}", s, err))
     }
 }
 
 impl Display for State {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        // FIXME use a foldLeft
-        let s: Vec<String> = self
-            .glasses
-            .clone()
-            .into_iter()
-            .map(|g| format!("{}", g))
-            .collect();
-        write!(f, "{}", s.join(", "))
+        for (index, glass) in self.glasses.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", glass)?;
+        }
+        Ok(())
     }
 }
 
@@ -156,21 +219,21 @@ mod tests {
         #[should_panic]
         fn create_state_with_invalid_string() {
             let s = "4/7, 3/a, 0/2";
-            State::from(s);
+            let _ = State::from(s);
         }
 
         #[test]
         #[should_panic]
         fn create_state_with_invalid_string_2() {
             let s = "4/70/2";
-            State::from(s);
+            let _ = State::from(s);
         }
 
         #[test]
         #[should_panic]
         fn create_state_with_invalid_string_3() {
             let s = "plop";
-            State::from(s);
+            let _ = State::from(s);
         }
     }
 
@@ -188,6 +251,64 @@ mod tests {
         }
     }
 
+    mod accessors {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn borrow_glasses() {
+            let state = State::from("4/7, 3/5, 0/2");
+
+            assert_eq!(state.len(), 3);
+            assert_eq!(state.get(1), Some(&Glass::new(3, 5)));
+            assert_eq!(state.get(3), None);
+            assert_eq!(state[2], Glass::new(0, 2));
+            let capacities: Vec<u32> = state.iter().map(|g| g.capacity).collect();
+            assert_eq!(capacities, vec![7, 5, 2]);
+            assert_eq!((&state).into_iter().count(), 3);
+        }
+
+        #[test]
+        fn totals() {
+            let state = State::from("4/7, 3/5, 0/2");
+
+            assert_eq!(state.total_volume(), 7);
+            assert_eq!(state.total_capacity(), 14);
+
+            let large = State::from("4000000000/4000000000, 1/4000000000");
+            assert_eq!(large.total_volume(), 4_000_000_001);
+            assert_eq!(large.total_capacity(), 8_000_000_000);
+        }
+
+        #[test]
+        #[should_panic]
+        fn index_out_of_bounds() {
+            let state = State::from("4/7");
+
+            let _ = &state[1];
+        }
+    }
+
+    mod builder {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn build_state() {
+            let state = State::builder().glass(4, 7).full(5).empty(2).build();
+
+            assert_eq!(state, State::from("4/7, 5/5, 0/2"));
+        }
+
+        #[test]
+        #[should_panic]
+        fn build_without_glass() {
+            let _ = State::builder().build();
+        }
+    }
+
     mod available_operations {
         use pretty_assertions::assert_eq;
 
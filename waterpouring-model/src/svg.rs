@@ -0,0 +1,449 @@
+use std::fmt::Write;
+
+use crate::labels::Labels;
+use crate::operations::Operation;
+use crate::operations::Operation::Pour;
+use crate::plan::Trace;
+use crate::state::State;
+
+// Room around the glasses, in pixels
+const MARGIN: f64 = 10.0;
+const GAP: f64 = 20.0;
+const CAPTION: f64 = 24.0;
+const ARROWS: f64 = 36.0;
+const LABEL: f64 = 20.0;
+
+const STYLE: &str = "<style>\
+.glass{fill:none;stroke:#555;stroke-width:2}\
+.water{fill:#4a90d9}\
+.label,.caption{font:12px sans-serif;text-anchor:middle}\
+.arrow{fill:none;stroke:#c0392b;stroke-width:2}\
+</style>";
+
+const DEFS: &str = "<defs>\
+<marker id=\"head\" viewBox=\"0 0 10 10\" refX=\"9\" refY=\"5\" markerWidth=\"6\" markerHeight=\"6\" orient=\"auto\">\
+<path d=\"M0,0L10,5L0,10z\" fill=\"#c0392b\"/>\
+</marker>\
+</defs>";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+    // height of the largest glass, in pixels
+    pub height: f64,
+    pub glass_width: f64,
+    // seconds for each step of the animation
+    pub step_duration: f64,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            height: 120.0,
+            glass_width: 40.0,
+            step_duration: 1.0,
+        }
+    }
+}
+
+// Glasses keep the same capacities along a trace, so positions are computed once
+struct Layout {
+    // left and height of each glass
+    glasses: Vec<(f64, f64)>,
+    glass_width: f64,
+    unit: f64,
+    width: f64,
+    height: f64,
+    bottom: f64,
+}
+
+impl Layout {
+    fn new(state: &State, options: &SvgOptions) -> Self {
+        let max_capacity = state.iter().map(|g| g.capacity).max().unwrap_or(1).max(1);
+        let unit = options.height / f64::from(max_capacity);
+        let glasses: Vec<(f64, f64)> = state
+            .iter()
+            .enumerate()
+            .map(|(index, glass)| {
+                let left = MARGIN + index as f64 * (options.glass_width + GAP);
+                (left, f64::from(glass.capacity) * unit)
+            })
+            .collect();
+        let count = glasses.len() as f64;
+        let bottom = CAPTION + ARROWS + options.height;
+
+        Self {
+            glasses,
+            glass_width: options.glass_width,
+            unit,
+            width: 2.0 * MARGIN + count * options.glass_width + (count - 1.0).max(0.0) * GAP,
+            height: bottom + LABEL + MARGIN,
+            bottom,
+        }
+    }
+
+    fn center(&self, index: usize) -> f64 {
+        self.glasses[index].0 + self.glass_width / 2.0
+    }
+
+    fn top(&self, index: usize) -> f64 {
+        self.bottom - self.glasses[index].1
+    }
+
+    // a non empty glass should show some water
+    fn level(&self, current: u32) -> f64 {
+        match current {
+            0 => 0.0,
+            _ => (f64::from(current) * self.unit).max(1.0),
+        }
+    }
+}
+
+fn num(value: f64) -> String {
+    let text = format!("{:.3}", value);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn document(width: f64, height: f64, body: &str) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n{}\n{}\n{}</svg>\n",
+        STYLE,
+        DEFS,
+        body,
+        w = num(width),
+        h = num(height)
+    )
+}
+
+fn caption(out: &mut String, layout: &Layout, text: &str) {
+    writeln!(
+        out,
+        "<text class=\"caption\" x=\"{}\" y=\"16\">{}</text>",
+        num(layout.width / 2.0),
+        escape(text)
+    )
+    .unwrap();
+}
+
+fn outlines(out: &mut String, layout: &Layout) {
+    for (index, (left, height)) in layout.glasses.iter().enumerate() {
+        writeln!(
+            out,
+            "<rect class=\"glass\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/>",
+            num(*left),
+            num(layout.top(index)),
+            num(layout.glass_width),
+            num(*height)
+        )
+        .unwrap();
+    }
+}
+
+fn water(out: &mut String, layout: &Layout, state: &State) {
+    for ((left, _), glass) in layout.glasses.iter().zip(state.iter()) {
+        let level = layout.level(glass.current);
+        writeln!(
+            out,
+            "<rect class=\"water\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/>",
+            num(*left),
+            num(layout.bottom - level),
+            num(layout.glass_width),
+            num(level)
+        )
+        .unwrap();
+    }
+}
+
+fn labels(out: &mut String, layout: &Layout, state: &State, names: &Labels) {
+    for (index, glass) in state.iter().enumerate() {
+        writeln!(
+            out,
+            "<text class=\"label\" x=\"{}\" y=\"{}\">{}</text>",
+            num(layout.center(index)),
+            num(layout.bottom + 16.0),
+            escape(&names.glass(index, glass))
+        )
+        .unwrap();
+    }
+}
+
+// Curved arrow above the glasses, nothing for other operations
+fn arrow(out: &mut String, layout: &Layout, operation: &Operation) {
+    if let Pour { from, to } = *operation {
+        writeln!(
+            out,
+            "<path class=\"arrow\" d=\"M{},{} Q{},{} {},{}\" marker-end=\"url(#head)\"/>",
+            num(layout.center(from)),
+            num(layout.top(from) - 4.0),
+            num((layout.center(from) + layout.center(to)) / 2.0),
+            num(CAPTION + 4.0),
+            num(layout.center(to)),
+            num(layout.top(to) - 4.0)
+        )
+        .unwrap();
+    }
+}
+
+fn frame(
+    layout: &Layout,
+    state: &State,
+    names: &Labels,
+    title: &str,
+    operation: Option<&Operation>,
+) -> String {
+    let mut out = String::new();
+    caption(&mut out, layout, title);
+    water(&mut out, layout, state);
+    outlines(&mut out, layout);
+    labels(&mut out, layout, state, names);
+    if let Some(operation) = operation {
+        arrow(&mut out, layout, operation);
+    }
+    out
+}
+
+fn titles(trace: &Trace) -> Vec<(String, &State, Option<&Operation>)> {
+    let mut result = vec![(String::from("Start"), trace.start(), None)];
+    for (index, step) in trace.iter().enumerate() {
+        let title = format!(
+            "{}. {}",
+            index + 1,
+            trace.labels().operation(&step.operation)
+        );
+        result.push((title, &step.after, Some(&step.operation)));
+    }
+    result
+}
+
+pub fn state_svg(state: &State, options: &SvgOptions) -> String {
+    labelled_state_svg(state, &Labels::default(), options)
+}
+
+pub fn labelled_state_svg(state: &State, names: &Labels, options: &SvgOptions) -> String {
+    let layout = Layout::new(state, options);
+    let mut body = String::new();
+    water(&mut body, &layout, state);
+    outlines(&mut body, &layout);
+    labels(&mut body, &layout, state, names);
+
+    document(layout.width, layout.height, &body)
+}
+
+// Every step side by side, the pour arrow is drawn on the resulting state
+pub fn filmstrip_svg(trace: &Trace, options: &SvgOptions) -> String {
+    let layout = Layout::new(trace.start(), options);
+    let frames = titles(trace);
+    let mut body = String::new();
+    for (index, (title, state, operation)) in frames.iter().enumerate() {
+        writeln!(
+            body,
+            "<g transform=\"translate({},0)\">",
+            num(index as f64 * layout.width)
+        )
+        .unwrap();
+        body.push_str(&frame(&layout, state, trace.labels(), title, *operation));
+        body.push_str("</g>\n");
+    }
+
+    document(layout.width * frames.len() as f64, layout.height, &body)
+}
+
+// Only show the `index`th of `count` intervals of the animation
+fn visible(index: usize, count: usize, duration: &str) -> String {
+    let (start, end) = (
+        index as f64 / count as f64,
+        (index + 1) as f64 / count as f64,
+    );
+    let (values, times) = if index == 0 {
+        ("1;0".to_string(), format!("0;{}", num(end)))
+    } else if index + 1 == count {
+        ("0;1".to_string(), format!("0;{}", num(start)))
+    } else {
+        (
+            "0;1;0".to_string(),
+            format!("0;{};{}", num(start), num(end)),
+        )
+    };
+    format!(
+        "<animate attributeName=\"opacity\" values=\"{}\" keyTimes=\"{}\" calcMode=\"discrete\" dur=\"{}\" repeatCount=\"indefinite\"/>",
+        values, times, duration
+    )
+}
+
+// SMIL animation: the start, one interval for each step, then the end is held before looping
+pub fn animated_svg(trace: &Trace, options: &SvgOptions) -> String {
+    let layout = Layout::new(trace.start(), options);
+    let mut frames = titles(trace);
+    frames.push((String::from("Done"), trace.end(), None));
+    let count = frames.len();
+    let duration = format!("{}s", num(count as f64 * options.step_duration));
+
+    // the water moves during each step, the start and the end are held
+    let times: Vec<String> = (0..=count).map(|i| num(i as f64 / count as f64)).collect();
+    let times = times.join(";");
+    let mut states: Vec<&State> = frames.iter().map(|(_, state, _)| *state).collect();
+    states.insert(0, trace.start());
+
+    let mut body = String::new();
+    for (index, (left, _)) in layout.glasses.iter().enumerate() {
+        let levels: Vec<f64> = states
+            .iter()
+            .map(|state| layout.level(state[index].current))
+            .collect();
+        let heights: Vec<String> = levels.iter().map(|level| num(*level)).collect();
+        let tops: Vec<String> = levels
+            .iter()
+            .map(|level| num(layout.bottom - level))
+            .collect();
+        writeln!(
+            body,
+            "<rect class=\"water\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\">",
+            num(*left),
+            tops[0],
+            num(layout.glass_width),
+            heights[0]
+        )
+        .unwrap();
+        for (attribute, values) in [("y", &tops), ("height", &heights)] {
+            writeln!(
+                body,
+                "<animate attributeName=\"{}\" values=\"{}\" keyTimes=\"{}\" dur=\"{}\" repeatCount=\"indefinite\"/>",
+                attribute,
+                values.join(";"),
+                times,
+                duration
+            )
+            .unwrap();
+        }
+        body.push_str("</rect>\n");
+    }
+    outlines(&mut body, &layout);
+
+    for (index, (title, state, operation)) in frames.iter().enumerate() {
+        let opacity = if index == 0 { 1 } else { 0 };
+        writeln!(body, "<g opacity=\"{}\">", opacity).unwrap();
+        writeln!(body, "{}", visible(index, count, &duration)).unwrap();
+        caption(&mut body, &layout, title);
+        labels(&mut body, &layout, state, trace.labels());
+        if let Some(operation) = operation {
+            arrow(&mut body, &layout, operation);
+        }
+        body.push_str("</g>\n");
+    }
+
+    document(layout.width, layout.height, &body)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::plan::Plan;
+    use crate::problem::Problem;
+
+    use super::*;
+
+    fn trace() -> Trace {
+        let problem = Problem::from(("0/4, 0/2", "2/4, 0/2"));
+        let operations = vec![
+            Operation::fill(0),
+            Operation::pour(0, 1),
+            Operation::empty(1),
+        ];
+        Plan::new(problem, operations).validate().unwrap()
+    }
+
+    mod state_svg {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn glasses_proportional_to_capacity() {
+            let state = State::from("3/4, 0/2");
+
+            let result = state_svg(&state, &SvgOptions::default());
+
+            assert!(result.starts_with(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"120\" height=\"210\""
+            ));
+            assert!(result
+                .contains("<rect class=\"glass\" x=\"10\" y=\"60\" width=\"40\" height=\"120\"/>"));
+            assert!(result
+                .contains("<rect class=\"glass\" x=\"70\" y=\"120\" width=\"40\" height=\"60\"/>"));
+            assert!(result
+                .contains("<rect class=\"water\" x=\"10\" y=\"90\" width=\"40\" height=\"90\"/>"));
+            assert!(result.contains(">3/4</text>"));
+            assert!(result.ends_with("</svg>\n"));
+        }
+
+        #[test]
+        fn small_volume_is_visible() {
+            let state = State::from("1/1000, 0/2");
+
+            let result = state_svg(&state, &SvgOptions::default());
+
+            assert!(result.contains("y=\"179\" width=\"40\" height=\"1\"/>"));
+        }
+
+        #[test]
+        fn format_numbers() {
+            assert_eq!(num(120.0), "120");
+            assert_eq!(num(1.0 / 3.0), "0.333");
+            assert_eq!(num(0.5), "0.5");
+            assert_eq!(num(0.0), "0");
+        }
+    }
+
+    mod trace_svg {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn filmstrip() {
+            let result = filmstrip_svg(&trace(), &SvgOptions::default());
+
+            assert_eq!(result.matches("<g transform").count(), 4);
+            assert_eq!(result.matches("class=\"arrow\"").count(), 1);
+            assert!(result.contains(">2. Pour(0-&gt;1)</text>"));
+            assert!(result.contains("<path class=\"arrow\" d=\"M30,56 Q60,28 90,116\""));
+        }
+
+        #[test]
+        fn labelled_filmstrip() {
+            // names given through `Labels` are not restricted like parsed ones
+            let labels = Labels::new(
+                vec![Some("a&b".into()), Some("cup".into())],
+                Some("cL".into()),
+            );
+            let problem = Problem::from(("0/4, 0/2", "4/4, 0/2")).with_labels(labels);
+            let trace = Plan::new(problem, vec![Operation::fill(0)])
+                .validate()
+                .unwrap();
+
+            let result = filmstrip_svg(&trace, &SvgOptions::default());
+
+            assert!(result.contains(">1. Fill(a&amp;b)</text>"));
+            assert!(result.contains(">cup:0/2cL</text>"));
+        }
+
+        #[test]
+        fn animation() {
+            let result = animated_svg(&trace(), &SvgOptions::default());
+
+            // start, 3 steps and the end
+            assert_eq!(result.matches("<g opacity").count(), 5);
+            assert_eq!(result.matches("class=\"arrow\"").count(), 1);
+            assert!(result.contains(
+                "<animate attributeName=\"height\" values=\"0;0;120;60;60;60\" keyTimes=\"0;0.2;0.4;0.6;0.8;1\" dur=\"5s\""
+            ));
+            assert!(
+                result.contains("values=\"0;1;0\" keyTimes=\"0;0.4;0.6\" calcMode=\"discrete\"")
+            );
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use crate::operations::GlassId;
+use crate::problem::Problem;
+use crate::state::State;
+
+// Groups of glasses that can be swapped without changing the problem
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symmetry {
+    groups: Vec<Vec<GlassId>>,
+}
+
+impl Symmetry {
+    pub fn none() -> Self {
+        Self { groups: vec![] }
+    }
+
+    // Glasses with the same capacity and the same target are interchangeable,
+    // so the target state is left unchanged by any permutation of a group
+    pub fn of_problem(problem: &Problem) -> Self {
+        let (from, to) = (&problem.from, &problem.to);
+        if from.len() != to.len() {
+            return Self::none();
+        }
+        let mut by_key: HashMap<(u32, u32), Vec<GlassId>> = HashMap::new();
+        for (index, (g1, g2)) in from.iter().zip(to).enumerate() {
+            by_key
+                .entry((g1.capacity, g2.current))
+                .or_default()
+                .push(index);
+        }
+        let mut groups: Vec<Vec<GlassId>> = by_key
+            .into_values()
+            .filter(|group| group.len() > 1)
+            .collect();
+        groups.sort();
+
+        Self { groups }
+    }
+
+    pub fn is_trivial(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    // Glasses of each group are sorted by volume
+    pub fn canonical(&self, state: &State) -> State {
+        if self.is_trivial() {
+            return state.clone();
+        }
+        let mut glasses = state.glasses();
+        for group in &self.groups {
+            let mut sorted: Vec<GlassId> = group.clone();
+            sorted.sort_by_key(|&index| state[index].current);
+            for (&position, &index) in group.iter().zip(sorted.iter()) {
+                glasses[position] = state[index].clone();
+            }
+        }
+
+        State::new(glasses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod canonical {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn no_equal_capacity() {
+            let problem = Problem::from(("3/5, 1/3", "4/5, 0/3"));
+            let symmetry = Symmetry::of_problem(&problem);
+
+            assert!(symmetry.is_trivial());
+            assert_eq!(symmetry.canonical(&problem.from), problem.from);
+        }
+
+        #[test]
+        fn sort_equal_capacity_glasses() {
+            let problem = Problem::from(("3/4, 1/4, 0/3", "0/4, 0/4, 2/3"));
+            let symmetry = Symmetry::of_problem(&problem);
+
+            assert_eq!(
+                symmetry.canonical(&problem.from),
+                State::from("1/4, 3/4, 0/3")
+            );
+        }
+
+        #[test]
+        fn same_canonical_for_swapped_glasses() {
+            let problem = Problem::from(("2/4, 1/3, 4/4", "0/4, 1/3, 0/4"));
+            let symmetry = Symmetry::of_problem(&problem);
+            let swapped = State::from("4/4, 1/3, 2/4");
+
+            assert_eq!(
+                symmetry.canonical(&problem.from),
+                symmetry.canonical(&swapped)
+            );
+        }
+
+        #[test]
+        fn problem_keeps_target_glasses_apart() {
+            let problem = Problem::from(("0/4, 0/4, 0/3", "2/4, 0/4, 0/3"));
+            let symmetry = Symmetry::of_problem(&problem);
+
+            assert!(symmetry.is_trivial());
+        }
+
+        #[test]
+        fn problem_groups_glasses_with_same_target() {
+            let problem = Problem::from(("4/4, 0/4, 0/4, 0/3", "0/4, 2/4, 2/4, 0/3"));
+            let symmetry = Symmetry::of_problem(&problem);
+
+            assert_eq!(
+                symmetry.canonical(&State::from("0/4, 4/4, 0/4, 3/3")),
+                State::from("0/4, 0/4, 4/4, 3/3")
+            );
+        }
+    }
+}
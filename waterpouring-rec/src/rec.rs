@@ -3,9 +3,11 @@ use std::hash::BuildHasher;
 
 use waterpouring_model::operations::Operation;
 use waterpouring_model::problem::{check_solvable, Problem};
+use waterpouring_model::solver::Evidence::Exhausted;
 use waterpouring_model::solver::SolverError::UnsolvableProblem;
-use waterpouring_model::solver::{Solver, SolverError, SolverResult, StateWithHistory};
+use waterpouring_model::solver::{Limits, Solution, Solver, SolverError, StateWithHistory, Stats};
 use waterpouring_model::state::State;
+use waterpouring_model::symmetry::Symmetry;
 
 #[derive(Debug)]
 pub struct RecSolver();
@@ -16,61 +18,90 @@ impl RecSolver {
         problem: &Problem,
         state_with_history: StateWithHistory,
         visited: &mut HashSet<State, S>,
-    ) -> SolverResult {
+        symmetry: &Symmetry,
+        limits: &Limits,
+        depth: usize,
+    ) -> Result<Solution, SolverError> {
         // check if we found a solution
         let maybe_solution = state_with_history
             .clone()
             .into_iter()
             .find(|(state, _)| *state == problem.to);
+        let stats = Stats {
+            depth,
+            visited: visited.len(),
+        };
         if let Some(result) = maybe_solution {
-            return Ok(result.1);
+            return Ok(Solution {
+                operations: result.1,
+                stats,
+            });
         }
+        limits.check(problem, stats)?;
 
         // Find next states
         let mut new_states_with_history: StateWithHistory = vec![];
         let initial_visited_size = visited.len();
         for (state, history) in state_with_history {
-            Self::process_state_history(&mut new_states_with_history, visited, &state, &history);
+            Self::process_state_history(
+                &mut new_states_with_history,
+                visited,
+                symmetry,
+                &state,
+                &history,
+            );
         }
 
         // check visited
         if initial_visited_size == visited.len() {
             return Err(UnsolvableProblem {
                 problem: problem.to_string(),
+                evidence: Some(Exhausted {
+                    visited: visited.len(),
+                }),
             });
         }
 
         // TailCall
-        self.solve_aux(problem, new_states_with_history, visited)
+        self.solve_aux(
+            problem,
+            new_states_with_history,
+            visited,
+            symmetry,
+            limits,
+            depth + 1,
+        )
     }
 
     fn process_state_history<S: BuildHasher>(
         new_states_with_history: &mut StateWithHistory,
         visited: &mut HashSet<State, S>,
+        symmetry: &Symmetry,
         state: &State,
         history: &[Operation],
     ) {
         let operations = state.available_operations();
         for op in operations {
             let new_state = state.apply(op);
-            if !visited.contains(&new_state) {
+            // visited states are stored in canonical form
+            if visited.insert(symmetry.canonical(&new_state)) {
                 let mut new_history = history.to_owned();
                 new_history.push(op);
-                new_states_with_history.push((new_state.clone(), new_history));
-                visited.insert(new_state);
+                new_states_with_history.push((new_state, new_history));
             }
         }
     }
 }
 
 impl Solver for RecSolver {
-    fn solve(&self, problem: Problem) -> Result<Vec<Operation>, SolverError> {
+    fn solve_with(&self, problem: Problem, limits: &Limits) -> Result<Solution, SolverError> {
         let problem = check_solvable(&problem)?;
         let start: StateWithHistory = vec![(problem.from.clone(), vec![])];
+        let symmetry = limits.symmetry(problem);
         let mut set = HashSet::new();
-        set.insert(problem.from.clone());
+        set.insert(symmetry.canonical(&problem.from));
 
-        self.solve_aux(problem, start, &mut set)
+        self.solve_aux(problem, start, &mut set, &symmetry, limits, 0)
     }
 }
 
@@ -79,6 +110,7 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use waterpouring_model::solver::test_solver;
+    use waterpouring_model::solver::InvalidReason::GlassCountMismatch;
     use waterpouring_model::solver::SolverError::{InvalidProblem, UnsolvableProblem};
 
     use super::*;
@@ -105,11 +137,11 @@ mod tests {
         let solver = RecSolver();
         let from = State::from("0/8, 0/4, 0/2");
         let to = State::from("0/4, 0/2");
-        let problem = Problem { from, to };
+        let problem = Problem::new(from, to);
 
         let result = solver.solve(problem.clone());
 
-        let reason = "Should have same number of glasses".to_string();
+        let reason = GlassCountMismatch { from: 3, to: 2 };
         assert_eq!(
             result,
             Err(InvalidProblem {
@@ -124,7 +156,7 @@ mod tests {
         let solver = RecSolver();
         let from = State::from("0/8, 0/4, 0/2");
         let to = State::from("1/8, 0/4, 0/2");
-        let problem = Problem { from, to };
+        let problem = Problem::new(from, to);
 
         let result = solver.solve(problem.clone());
 
@@ -132,7 +164,8 @@ mod tests {
         assert_eq!(
             result,
             Err(UnsolvableProblem {
-                problem: problem.to_string()
+                problem: problem.to_string(),
+                evidence: Some(Exhausted { visited: 30 })
             })
         )
     }
@@ -3,9 +3,11 @@ use std::hash::BuildHasher;
 
 use waterpouring_model::operations::Operation;
 use waterpouring_model::problem::{check_solvable, Problem};
+use waterpouring_model::solver::Evidence::Exhausted;
 use waterpouring_model::solver::SolverError::UnsolvableProblem;
-use waterpouring_model::solver::{Solver, SolverError, SolverResult, StateWithHistory};
+use waterpouring_model::solver::{Limits, Solution, Solver, SolverError, StateWithHistory, Stats};
 use waterpouring_model::state::State;
+use waterpouring_model::symmetry::Symmetry;
 
 #[derive(Debug)]
 pub struct Rec2Solver();
@@ -16,54 +18,85 @@ impl Rec2Solver {
         problem: &Problem,
         state_with_history: StateWithHistory,
         visited: &mut HashSet<State, S>,
-    ) -> SolverResult {
+        symmetry: &Symmetry,
+        limits: &Limits,
+        depth: usize,
+    ) -> Result<Solution, SolverError> {
         let mut new_states_with_history: StateWithHistory = vec![];
         let initial_visited_size = visited.len();
+        let stats = Stats {
+            depth,
+            visited: initial_visited_size,
+        };
 
         for (state, history) in state_with_history {
             if state == problem.to {
-                return Ok(history);
+                return Ok(Solution {
+                    operations: history,
+                    stats,
+                });
             }
-            Self::process_state_history(&mut new_states_with_history, visited, &state, &history);
+            Self::process_state_history(
+                &mut new_states_with_history,
+                visited,
+                symmetry,
+                &state,
+                &history,
+            );
         }
 
+        // the whole level is checked for the target before applying limits
+        limits.check(problem, stats)?;
+
         // check visited
         if initial_visited_size == visited.len() {
             return Err(UnsolvableProblem {
                 problem: problem.to_string(),
+                evidence: Some(Exhausted {
+                    visited: visited.len(),
+                }),
             });
         }
         // TailCall
-        self.solve_aux(problem, new_states_with_history, visited)
+        self.solve_aux(
+            problem,
+            new_states_with_history,
+            visited,
+            symmetry,
+            limits,
+            depth + 1,
+        )
     }
 
     fn process_state_history<S: BuildHasher>(
         new_states_with_history: &mut StateWithHistory,
         visited: &mut HashSet<State, S>,
+        symmetry: &Symmetry,
         state: &State,
         history: &[Operation],
     ) {
         let operations = state.available_operations();
         for op in operations {
             let new_state = state.apply(op);
-            if !visited.contains(&new_state) {
+            // visited states are stored in canonical form
+            if visited.insert(symmetry.canonical(&new_state)) {
                 let mut new_history = history.to_owned();
                 new_history.push(op);
-                new_states_with_history.push((new_state.clone(), new_history));
-                visited.insert(new_state);
+                new_states_with_history.push((new_state, new_history));
             }
         }
     }
 }
 
 impl Solver for Rec2Solver {
-    fn solve(&self, problem: Problem) -> Result<Vec<Operation>, SolverError> {
+    fn solve_with(&self, problem: Problem, limits: &Limits) -> Result<Solution, SolverError> {
         let problem = check_solvable(&problem)?;
         let start: StateWithHistory = vec![(problem.from.clone(), vec![])];
+        let symmetry = limits.symmetry(problem);
         let mut set = HashSet::new();
-        set.insert(problem.from.clone());
+        set.insert(symmetry.canonical(&problem.from));
 
-        self.solve_aux(problem, start, &mut set)
+        self.solve_aux(problem, start, &mut set, &symmetry, limits, 0)
     }
 }
 
@@ -72,6 +105,7 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use waterpouring_model::solver::test_solver;
+    use waterpouring_model::solver::InvalidReason::GlassCountMismatch;
     use waterpouring_model::solver::SolverError::InvalidProblem;
 
     use super::*;
@@ -98,11 +132,11 @@ mod tests {
         let solver = Rec2Solver();
         let from = State::from("0/8, 0/4, 0/2");
         let to = State::from("0/4, 0/2");
-        let problem = Problem { from, to };
+        let problem = Problem::new(from, to);
 
         let result = solver.solve(problem.clone());
 
-        let reason = "Should have same number of glasses".to_string();
+        let reason = GlassCountMismatch { from: 3, to: 2 };
         assert_eq!(
             result,
             Err(InvalidProblem {
@@ -117,7 +151,7 @@ mod tests {
         let solver = Rec2Solver();
         let from = State::from("0/8, 0/4, 0/2");
         let to = State::from("1/8, 0/4, 0/2");
-        let problem = Problem { from, to };
+        let problem = Problem::new(from, to);
 
         let result = solver.solve(problem.clone());
 
@@ -125,7 +159,8 @@ mod tests {
         assert_eq!(
             result,
             Err(UnsolvableProblem {
-                problem: problem.to_string()
+                problem: problem.to_string(),
+                evidence: Some(Exhausted { visited: 30 })
             })
         )
     }
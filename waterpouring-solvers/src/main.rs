@@ -0,0 +1,208 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::io::IsTerminal;
+use std::process;
+use std::time::Duration;
+
+use waterpouring_model::ascii::{animate, AnimationOptions};
+use waterpouring_model::graph::{GraphOptions, Highlight, StateGraph};
+use waterpouring_model::notation::Notation;
+use waterpouring_model::operations::Operation;
+use waterpouring_model::parse::{parse_plan, ParseError};
+use waterpouring_model::plan::Plan;
+use waterpouring_model::problem::Problem;
+use waterpouring_model::solver::solve_animated;
+use waterpouring_model::svg::{animated_svg, filmstrip_svg, SvgOptions};
+use waterpouring_solvers::registry;
+use waterpouring_solvers::registry::SolverEntry;
+
+fn usage() -> ! {
+    eprintln!("Usage: waterpouring [options] <from> <to>");
+    eprintln!("       e.g. waterpouring '0/5, 0/3' '4/5, 0/3', or with names and unit 'jug:0/5L, cup:0/3L' '4/5, 0/3'");
+    eprintln!("       volumes can be decimal, e.g. '0/2.5, 0/0.75' '1/2.5, 0/0.75'");
+    eprintln!("       waterpouring --list");
+    eprintln!("Options:");
+    eprintln!("  --solver <name>     solver to use");
+    eprintln!("  --delay <ms>        animate the solution in the terminal");
+    eprintln!("  --dot <file>        export the state graph with Graphviz");
+    eprintln!("  --mermaid <file>    export the state graph with Mermaid");
+    eprintln!("  --max-nodes <n>     maximum number of states in the exported graph");
+    eprintln!("  --self-loops        keep operations that change nothing in the graph");
+    eprintln!("  --plan <operations> check a plan instead of solving, e.g. 'Fill(0) Pour(0->1)' or 'fill A, pour A->B'");
+    eprintln!("                      without any export");
+    eprintln!("  --svg <file>        export the solution as an animated SVG");
+    eprintln!("  --filmstrip <file>  export the solution steps side by side in an SVG");
+    eprintln!("Solvers: {}", registry::names().join(", "));
+    process::exit(1)
+}
+
+fn write_file(file: &str, content: String) {
+    if let Err(error) = fs::write(file, content) {
+        eprintln!("Cannot write {}, {}", file, error);
+        process::exit(1)
+    }
+}
+
+fn export(
+    entry: &SolverEntry,
+    problem: &Problem,
+    options: &GraphOptions,
+    dot: Option<String>,
+    mermaid: Option<String>,
+) {
+    let solver = entry.create();
+    // the graph is still exported, without a highlighted solution
+    let solution = solver.solve(problem.clone()).unwrap_or_else(|error| {
+        eprintln!("No solution highlighted, {}", error);
+        vec![]
+    });
+    let highlight = Highlight::new(problem, &solution);
+    let graph = StateGraph::explore(&problem.from, options);
+    if graph.is_truncated() {
+        eprintln!("Graph truncated to {} states", graph.nodes().len());
+    }
+
+    if let Some(file) = dot {
+        write_file(&file, graph.to_dot(&highlight));
+    }
+    if let Some(file) = mermaid {
+        write_file(&file, graph.to_mermaid(&highlight));
+    }
+}
+
+fn export_svg(
+    entry: &SolverEntry,
+    problem: &Problem,
+    svg: Option<String>,
+    filmstrip: Option<String>,
+) {
+    let solver = entry.create();
+    let solution = match solver.solve(problem.clone()) {
+        Ok(solution) => solution,
+        Err(error) => {
+            eprintln!("No SVG exported, {}", error);
+            return;
+        }
+    };
+    let trace = match Plan::new(problem.clone(), solution).validate() {
+        Ok(trace) => trace,
+        Err(error) => {
+            eprintln!("Invalid solution, {}", error.describe(&problem.labels));
+            process::exit(1)
+        }
+    };
+    let options = SvgOptions::default();
+
+    if let Some(file) = svg {
+        write_file(&file, animated_svg(&trace, &options));
+    }
+    if let Some(file) = filmstrip {
+        write_file(&file, filmstrip_svg(&trace, &options));
+    }
+}
+
+// `Fill(0) Pour(0->1)`, or the human notation `fill A, pour A->B`
+fn read_plan(problem: &Problem, plan: &str) -> Result<Vec<Operation>, ParseError> {
+    if plan.contains('(') {
+        parse_plan(plan)
+    } else {
+        Notation::of(problem).parse_plan(plan)
+    }
+}
+
+fn replay(problem: Problem, plan: &str, delay: Option<Duration>) {
+    println!("Check {}", problem);
+    let operations = match read_plan(&problem, plan) {
+        Ok(operations) => operations,
+        Err(error) => {
+            eprintln!("Invalid plan, {}", error);
+            process::exit(1)
+        }
+    };
+    let labels = problem.labels.clone();
+    match Plan::new(problem, operations).validate() {
+        Err(error) => {
+            eprintln!("Invalid plan, {}", error.describe(&labels));
+            process::exit(1)
+        }
+        Ok(trace) => {
+            let mut out = io::stdout();
+            let options = AnimationOptions {
+                delay,
+                tty: out.is_terminal(),
+            };
+            animate(&mut out, &trace, &options).expect("Cannot write the plan");
+        }
+    }
+}
+
+fn main() {
+    let mut entry = registry::default_solver();
+    let mut states: Vec<String> = vec![];
+    let mut dot: Option<String> = None;
+    let mut mermaid: Option<String> = None;
+    let mut svg: Option<String> = None;
+    let mut filmstrip: Option<String> = None;
+    let mut plan: Option<String> = None;
+    let mut options = GraphOptions::default();
+    let mut delay: Option<Duration> = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--list" => {
+                for solver in registry::solvers() {
+                    println!("{}", solver.info);
+                }
+                return;
+            }
+            "--solver" => {
+                let name = args.next().unwrap_or_else(|| usage());
+                entry = registry::find(&name).unwrap_or_else(|| usage());
+            }
+            "--delay" => {
+                let value = args.next().unwrap_or_else(|| usage());
+                let millis = value.parse().unwrap_or_else(|_| usage());
+                delay = Some(Duration::from_millis(millis));
+            }
+            "--dot" => dot = Some(args.next().unwrap_or_else(|| usage())),
+            "--mermaid" => mermaid = Some(args.next().unwrap_or_else(|| usage())),
+            "--max-nodes" => {
+                let value = args.next().unwrap_or_else(|| usage());
+                options.max_nodes = value.parse().unwrap_or_else(|_| usage());
+            }
+            "--self-loops" => options.collapse_self_loops = false,
+            "--plan" => plan = Some(args.next().unwrap_or_else(|| usage())),
+            "--svg" => svg = Some(args.next().unwrap_or_else(|| usage())),
+            "--filmstrip" => filmstrip = Some(args.next().unwrap_or_else(|| usage())),
+            _ => states.push(arg),
+        }
+    }
+
+    if states.len() != 2 {
+        usage();
+    }
+    // glasses can be named, with a unit and decimal volumes, e.g. `jug:0/5L, cup:0/0.5L`
+    let problem = Problem::parse(&states[0], &states[1]).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1)
+    });
+
+    if let Some(plan) = plan {
+        // exports are built from the solver solution, not from the checked plan
+        if dot.is_some() || mermaid.is_some() || svg.is_some() || filmstrip.is_some() {
+            eprintln!("--plan cannot be combined with --dot, --mermaid, --svg or --filmstrip");
+            process::exit(1)
+        }
+        return replay(problem, &plan, delay);
+    }
+    if dot.is_some() || mermaid.is_some() {
+        export(entry, &problem, &options, dot, mermaid);
+    }
+    if svg.is_some() || filmstrip.is_some() {
+        export_svg(entry, &problem, svg, filmstrip);
+    }
+
+    solve_animated(entry.create().as_ref(), problem, delay)
+}
@@ -0,0 +1,249 @@
+use std::fmt::{Display, Error, Formatter};
+
+use waterpouring_imp::imp::ImperativeSolver;
+use waterpouring_model::solver::Solver;
+use waterpouring_rec::rec::RecSolver;
+use waterpouring_rec2::rec2::Rec2Solver;
+
+use crate::registry::Feature::{AnyGlassCount, SymmetryReduction, UnsolvableDetection};
+use crate::registry::MemoryProfile::{Frontier, FrontierAndStack};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryProfile {
+    // the whole frontier is kept, each state with its history
+    Frontier,
+    // like `Frontier`, plus a stack frame for each level of the search
+    FrontierAndStack,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    AnyGlassCount,
+    SymmetryReduction,
+    UnsolvableDetection,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolverInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub optimal: bool,
+    pub memory: MemoryProfile,
+    pub features: &'static [Feature],
+}
+
+impl Display for SolverInfo {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let optimal = if self.optimal {
+            "optimal"
+        } else {
+            "not optimal"
+        };
+        write!(
+            f,
+            "{}: {} ({}, {:?}, {:?})",
+            self.name, self.description, optimal, self.memory, self.features
+        )
+    }
+}
+
+pub struct SolverEntry {
+    pub info: SolverInfo,
+    factory: fn() -> Box<dyn Solver>,
+}
+
+impl SolverEntry {
+    pub fn create(&self) -> Box<dyn Solver> {
+        (self.factory)()
+    }
+}
+
+const BFS_FEATURES: &[Feature] = &[AnyGlassCount, SymmetryReduction, UnsolvableDetection];
+
+static SOLVERS: &[SolverEntry] = &[
+    SolverEntry {
+        info: SolverInfo {
+            name: "rec",
+            description: "Recursive breadth first search",
+            optimal: true,
+            memory: FrontierAndStack,
+            features: BFS_FEATURES,
+        },
+        factory: || Box::new(RecSolver()),
+    },
+    SolverEntry {
+        info: SolverInfo {
+            name: "rec2",
+            description: "Recursive breadth first search, check target while expanding",
+            optimal: true,
+            memory: FrontierAndStack,
+            features: BFS_FEATURES,
+        },
+        factory: || Box::new(Rec2Solver()),
+    },
+    SolverEntry {
+        info: SolverInfo {
+            name: "imp",
+            description: "Imperative breadth first search",
+            optimal: true,
+            memory: Frontier,
+            features: BFS_FEATURES,
+        },
+        factory: || Box::new(ImperativeSolver()),
+    },
+];
+
+pub fn solvers() -> &'static [SolverEntry] {
+    SOLVERS
+}
+
+pub fn names() -> Vec<&'static str> {
+    solvers().iter().map(|entry| entry.info.name).collect()
+}
+
+pub fn find(name: &str) -> Option<&'static SolverEntry> {
+    solvers().iter().find(|entry| entry.info.name == name)
+}
+
+pub fn default_solver() -> &'static SolverEntry {
+    &solvers()[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use waterpouring_model::problem::Problem;
+    use waterpouring_model::solver::{test_solver, Limits};
+
+    use super::*;
+
+    #[test]
+    fn list_names() {
+        assert_eq!(names(), vec!["rec", "rec2", "imp"]);
+    }
+
+    #[test]
+    fn find_by_name() {
+        let entry = find("imp").expect("Should be registered");
+
+        assert_eq!(entry.info.name, "imp");
+        assert_eq!(entry.info.memory, Frontier);
+    }
+
+    #[test]
+    fn unknown_name() {
+        assert!(find("plop").is_none());
+    }
+
+    #[test]
+    fn create_solvers() {
+        for entry in solvers() {
+            let solver = entry.create();
+
+            assert_eq!(test_solver("0/5, 0/3", "4/5, 0/3", solver.as_ref()), 7);
+        }
+    }
+
+    #[test]
+    fn solve_with_stats() {
+        let problem = Problem::from(("0/5, 0/3", "4/5, 0/3"));
+
+        for entry in solvers() {
+            let solution = entry
+                .create()
+                .solve_with(problem.clone(), &Limits::default())
+                .expect("Should found a solution");
+
+            assert_eq!(solution.operations.len(), 7, "{}", entry.info.name);
+            assert_eq!(solution.stats.depth, 7, "{}", entry.info.name);
+            assert!(solution.stats.visited > 7, "{}", entry.info.name);
+        }
+    }
+
+    #[test]
+    fn stop_at_max_depth() {
+        let problem = Problem::from(("0/5, 0/3", "4/5, 0/3"));
+        let limits = |depth| Limits {
+            max_depth: Some(depth),
+            ..Limits::default()
+        };
+
+        for entry in solvers() {
+            let solver = entry.create();
+
+            let result = solver.solve_with(problem.clone(), &limits(6));
+
+            assert_eq!(
+                result.map_err(|err| err.code()).map(|_| ()),
+                Err("LIMIT_EXCEEDED"),
+                "{}",
+                entry.info.name
+            );
+            assert!(solver.solve_with(problem.clone(), &limits(7)).is_ok());
+        }
+    }
+
+    #[test]
+    fn same_length_with_symmetry_reduction() {
+        let problems = vec![
+            ("0/4, 0/4, 0/3", "2/4, 0/4, 0/3"),
+            ("0/4, 0/4, 0/3", "2/4, 2/4, 0/3"),
+            ("0/4, 0/4, 0/3", "1/4, 1/4, 0/3"),
+            ("8/8, 0/5, 0/5", "4/8, 2/5, 2/5"),
+            ("4/4, 0/4, 0/4, 0/3", "0/4, 2/4, 2/4, 0/3"),
+            ("0/6, 0/6, 0/6, 0/4", "1/6, 5/6, 0/6, 0/4"),
+            ("0/5, 0/5, 0/3, 0/3", "4/5, 4/5, 1/3, 0/3"),
+            ("0/4, 0/4, 0/4", "0/4, 0/4, 3/4"),
+        ];
+        let full = Limits {
+            symmetric_states: true,
+            ..Limits::default()
+        };
+
+        // solution lengths, or error codes for unsolvable problems
+        for entry in solvers() {
+            let solver = entry.create();
+            for &(from, to) in &problems {
+                let problem = Problem::from((from, to));
+                let outcome = |limits: &Limits| {
+                    solver
+                        .solve_with(problem.clone(), limits)
+                        .map(|solution| solution.operations.len())
+                        .map_err(|err| err.code())
+                };
+
+                assert_eq!(
+                    outcome(&Limits::default()),
+                    outcome(&full),
+                    "{} on {}",
+                    entry.info.name,
+                    problem
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn visit_fewer_states_with_symmetry_reduction() {
+        let problem = Problem::from(("0/4, 0/4, 0/3", "1/4, 1/4, 0/3"));
+        let full = Limits {
+            symmetric_states: true,
+            ..Limits::default()
+        };
+
+        for entry in solvers() {
+            let solver = entry.create();
+            let visited = |limits: &Limits| {
+                let solution = solver.solve_with(problem.clone(), limits);
+                solution.expect("Should found a solution").stats.visited
+            };
+
+            assert!(
+                visited(&Limits::default()) < visited(&full),
+                "{}",
+                entry.info.name
+            );
+        }
+    }
+}
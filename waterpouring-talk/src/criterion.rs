@@ -0,0 +1,280 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use waterpouring_bench::alloc::{read_records, AllocRecord};
+
+use crate::error::{TalkError, TalkResult};
+use crate::include::read;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct BenchmarkId {
+    group_id: String,
+    function_id: Option<String>,
+    value_str: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct Estimate {
+    point_estimate: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct Estimates {
+    mean: Estimate,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measure {
+    pub function: String,
+    pub value: String,
+    // in nanoseconds
+    pub mean: f64,
+}
+
+// Read all `new` measures of a criterion group, sorted by value then function
+pub fn measures(criterion: &Path, group: &str) -> TalkResult<Vec<Measure>> {
+    let mut files = vec![];
+    find_benchmarks(&criterion.join(group), &mut files);
+
+    let mut result = vec![];
+    for file in files {
+        let id: BenchmarkId = parse(&file)?;
+        if id.group_id != group {
+            continue;
+        }
+        let estimates: Estimates = parse(&file.with_file_name("estimates.json"))?;
+        result.push(Measure {
+            function: id.function_id.unwrap_or_default(),
+            value: id.value_str.unwrap_or_default(),
+            mean: estimates.mean.point_estimate,
+        });
+    }
+    result.sort_by(|m1, m2| (&m1.value, &m1.function).cmp(&(&m2.value, &m2.function)));
+
+    Ok(result)
+}
+
+fn find_benchmarks(dir: &Path, files: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for path in entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+    {
+        if path.is_dir() && !path.ends_with("base") && !path.ends_with("report") {
+            find_benchmarks(&path, files);
+        } else if path.ends_with("new/benchmark.json") {
+            files.push(path);
+        }
+    }
+}
+
+fn parse<T: serde::de::DeserializeOwned>(path: &Path) -> TalkResult<T> {
+    serde_json::from_str(&read(path)?).map_err(|source| TalkError::Json {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+// One row per input value, one column per function
+pub fn to_markdown(measures: &[Measure]) -> String {
+    let mut functions: Vec<&str> = measures.iter().map(|m| m.function.as_str()).collect();
+    functions.sort();
+    functions.dedup();
+    let mut rows: BTreeMap<&str, Vec<Option<f64>>> = BTreeMap::new();
+    for measure in measures {
+        let row = rows
+            .entry(measure.value.as_str())
+            .or_insert_with(|| vec![None; functions.len()]);
+        let column = functions
+            .iter()
+            .position(|&f| f == measure.function)
+            .unwrap();
+        row[column] = Some(measure.mean);
+    }
+
+    let mut result = format!("| Input | {} |\n", functions.join(" | "));
+    result.push_str(&format!("|---|{}\n", "---:|".repeat(functions.len())));
+    for (value, row) in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|mean| mean.map_or_else(|| "-".into(), format_time))
+            .collect();
+        result.push_str(&format!("| {} | {} |\n", value, cells.join(" | ")));
+    }
+
+    result
+}
+
+pub fn table(criterion: &Path, group: &str) -> TalkResult<String> {
+    let measures = measures(criterion, group)?;
+    if measures.is_empty() {
+        return Ok(format!(
+            "> No criterion data for `{}`, run `cargo bench` first",
+            group
+        ));
+    }
+
+    Ok(to_markdown(&measures))
+}
+
+// Allocations recorded by the solvers bench, next to the mean time of the same benchmark
+pub fn allocations_to_markdown(records: &[AllocRecord], measures: &[Measure]) -> String {
+    let mut result = String::from(
+        "| Input | Solver | Mean | Allocations | Allocated | Peak |\n|---|---|---:|---:|---:|---:|\n",
+    );
+    for record in records {
+        let mean = measures
+            .iter()
+            .find(|m| m.function == record.function && m.value == record.value)
+            .map_or_else(|| "-".into(), |m| format_time(m.mean));
+        result.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            record.value,
+            record.function,
+            mean,
+            record.stats.allocations,
+            format_bytes(record.stats.bytes),
+            format_bytes(record.stats.peak)
+        ));
+    }
+
+    result
+}
+
+pub fn allocations_table(criterion: &Path, group: &str) -> TalkResult<String> {
+    let records = match read_records(criterion, group) {
+        Ok(records) => records,
+        Err(_) => {
+            return Ok(format!(
+                "> No allocation data for `{}`, run `cargo bench` first",
+                group
+            ))
+        }
+    };
+
+    Ok(allocations_to_markdown(
+        &records,
+        &measures(criterion, group)?,
+    ))
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    let units = [("B", 1.0), ("KiB", 1024.0), ("MiB", 1024.0 * 1024.0)];
+    let bytes = bytes as f64;
+    let (unit, scale) = units
+        .iter()
+        .rev()
+        .find(|(_, scale)| bytes >= *scale)
+        .unwrap_or(&units[0]);
+    format!("{:.1} {}", bytes / scale, unit)
+}
+
+pub fn format_time(nanos: f64) -> String {
+    let units = [("ns", 1.0), ("µs", 1e3), ("ms", 1e6), ("s", 1e9)];
+    let (unit, scale) = units
+        .iter()
+        .rev()
+        .find(|(_, scale)| nanos >= *scale)
+        .unwrap_or(&units[0]);
+    format!("{:.2} {}", nanos / scale, unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod format_time {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn human_readable_units() {
+            assert_eq!(format_time(12.0), "12.00 ns");
+            assert_eq!(format_time(1_520.0), "1.52 µs");
+            assert_eq!(format_time(3_000_000.0), "3.00 ms");
+            assert_eq!(format_time(2.5e9), "2.50 s");
+        }
+    }
+
+    mod allocations {
+        use pretty_assertions::assert_eq;
+        use waterpouring_bench::alloc::AllocStats;
+
+        use super::*;
+
+        #[test]
+        fn join_with_timings() {
+            let records = vec![AllocRecord {
+                function: "imp".into(),
+                value: "2".into(),
+                stats: AllocStats {
+                    allocations: 42,
+                    bytes: 2048,
+                    peak: 100,
+                },
+            }];
+            let measures = vec![Measure {
+                function: "imp".into(),
+                value: "2".into(),
+                mean: 1_500.0,
+            }];
+
+            assert_eq!(
+                allocations_to_markdown(&records, &measures),
+                "| Input | Solver | Mean | Allocations | Allocated | Peak |\n\
+                 |---|---|---:|---:|---:|---:|\n\
+                 | 2 | imp | 1.50 µs | 42 | 2.0 KiB | 100.0 B |\n"
+            );
+        }
+
+        #[test]
+        fn missing_data() {
+            let table = allocations_table(Path::new("does-not-exist"), "plop").unwrap();
+
+            assert!(table.contains("cargo bench"));
+        }
+    }
+
+    mod to_markdown {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        fn measure(function: &str, value: &str, mean: f64) -> Measure {
+            Measure {
+                function: function.into(),
+                value: value.into(),
+                mean,
+            }
+        }
+
+        #[test]
+        fn one_column_per_function() {
+            let measures = vec![
+                measure("imp", "2", 100.0),
+                measure("rec", "2", 2_000.0),
+                measure("rec", "4", 3_000.0),
+            ];
+
+            assert_eq!(
+                to_markdown(&measures),
+                "| Input | imp | rec |\n\
+                 |---|---:|---:|\n\
+                 | 2 | 100.00 ns | 2.00 µs |\n\
+                 | 4 | - | 3.00 µs |\n"
+            );
+        }
+
+        #[test]
+        fn missing_data() {
+            let table = table(Path::new("does-not-exist"), "plop").unwrap();
+
+            assert!(table.contains("cargo bench"));
+        }
+    }
+}
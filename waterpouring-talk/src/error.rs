@@ -0,0 +1,35 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum TalkError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    Directive {
+        directive: String,
+        reason: String,
+    },
+}
+
+impl Display for TalkError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            TalkError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            TalkError::Json { path, source } => write!(f, "{}: {}", path.display(), source),
+            TalkError::Directive { directive, reason } => {
+                write!(f, "Invalid directive '{}': {}", directive, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TalkError {}
+
+pub type TalkResult<T> = Result<T, TalkError>;
@@ -0,0 +1,350 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use waterpouring_bench::hyperfine::{changes_markdown, summary_markdown, Export};
+use waterpouring_model::plan::Plan;
+use waterpouring_model::problem::Problem;
+use waterpouring_model::svg::{animated_svg, filmstrip_svg, SvgOptions};
+use waterpouring_solvers::registry;
+
+use crate::criterion;
+use crate::error::{TalkError, TalkResult};
+
+// Where directives look for their files
+#[derive(Debug, Clone)]
+pub struct Context {
+    pub base: PathBuf,
+    pub criterion: PathBuf,
+}
+
+// Expand `{{#include ...}}`, `{{#criterion ...}}`, `{{#allocations ...}}`, `{{#hyperfine ...}}`,
+// `{{#svg ...}}` and `{{#filmstrip ...}}` lines into markdown
+pub fn expand(markdown: &str, context: &Context) -> TalkResult<String> {
+    let mut result = String::new();
+    for line in markdown.lines() {
+        match parse_directive(line.trim()) {
+            Some((name, argument)) => {
+                let expanded = match name {
+                    "include" => include(argument, context)?,
+                    "criterion" => criterion::table(&context.criterion, argument)?,
+                    "allocations" => criterion::allocations_table(&context.criterion, argument)?,
+                    "hyperfine" => hyperfine(argument, context)?,
+                    "svg" | "filmstrip" => svg(name, argument)?,
+                    _ => {
+                        return Err(TalkError::Directive {
+                            directive: line.trim().into(),
+                            reason: "unknown directive".into(),
+                        });
+                    }
+                };
+                result.push_str(&expanded);
+            }
+            None => result.push_str(line),
+        }
+        result.push('\n');
+    }
+
+    Ok(result)
+}
+
+fn parse_directive(line: &str) -> Option<(&str, &str)> {
+    let inner = line.strip_prefix("{{#")?.strip_suffix("}}")?.trim();
+    let (name, argument) = inner.split_at(inner.find(' ').unwrap_or(inner.len()));
+    Some((name, argument.trim()))
+}
+
+// `path`, `path:start:end` (1-based lines) or `path#item`
+fn include(argument: &str, context: &Context) -> TalkResult<String> {
+    let error = |reason: &str| TalkError::Directive {
+        directive: format!("{{{{#include {}}}}}", argument),
+        reason: reason.into(),
+    };
+
+    let (path, selection) = match argument.find([':', '#']) {
+        Some(index) => (&argument[..index], Some(&argument[index..])),
+        None => (argument, None),
+    };
+    let source = read(&context.base.join(path))?;
+    let code = match selection {
+        None => source,
+        Some(selection) if selection.starts_with('#') => {
+            extract_item(&source, &selection[1..]).ok_or_else(|| error("item not found"))?
+        }
+        Some(selection) => {
+            let bounds: Vec<usize> = selection[1..]
+                .split(':')
+                .map(|s| s.parse().map_err(|_| error("invalid line range")))
+                .collect::<TalkResult<_>>()?;
+            match bounds.as_slice() {
+                [start, end] if 0 < *start && start <= end => lines(&source, *start, *end),
+                _ => return Err(error("invalid line range")),
+            }
+        }
+    };
+
+    let lang = match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+        Some("rs") => "rust",
+        Some(ext) => ext,
+        None => "",
+    };
+    Ok(format!("```{}\n{}\n```", lang, code.trim_end()))
+}
+
+// `export` or `export baseline`, paths are relative to the markdown file
+fn hyperfine(argument: &str, context: &Context) -> TalkResult<String> {
+    let read = |path: &str| {
+        Export::read(context.base.join(path)).map_err(|err| TalkError::Directive {
+            directive: format!("{{{{#hyperfine {}}}}}", argument),
+            reason: err.to_string(),
+        })
+    };
+
+    let paths: Vec<&str> = argument.split_whitespace().collect();
+    let current = read(paths.first().copied().unwrap_or_default())?;
+    let mut result = summary_markdown(&current.summary());
+    if let Some(baseline) = paths.get(1) {
+        result.push('\n');
+        result.push_str(&changes_markdown(&current.compare(&read(baseline)?, 0.1)));
+    }
+
+    Ok(result)
+}
+
+// `from -> to`, the solution of the default solver is drawn in an inline SVG
+fn svg(name: &str, argument: &str) -> TalkResult<String> {
+    let error = |reason: String| TalkError::Directive {
+        directive: format!("{{{{#{} {}}}}}", name, argument),
+        reason,
+    };
+
+    let (from, to) = match argument.split_once("->") {
+        Some((from, to)) => (from.trim(), to.trim()),
+        None => return Err(error("expected 'from -> to'".into())),
+    };
+    let problem = Problem::parse(from, to).map_err(|err| error(err.to_string()))?;
+    let operations = registry::default_solver()
+        .create()
+        .solve(problem.clone())
+        .map_err(|err| error(err.to_string()))?;
+    let labels = problem.labels.clone();
+    let trace = Plan::new(problem, operations)
+        .validate()
+        .map_err(|err| error(err.describe(&labels)))?;
+
+    let options = SvgOptions::default();
+    let image = match name {
+        "filmstrip" => filmstrip_svg(&trace, &options),
+        _ => animated_svg(&trace, &options),
+    };
+    // raw HTML block, it must not contain blank lines
+    Ok(format!("<div class=\"svg\">\n{}</div>", image))
+}
+
+pub fn read(path: &Path) -> TalkResult<String> {
+    fs::read_to_string(path).map_err(|source| TalkError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn lines(source: &str, start: usize, end: usize) -> String {
+    let selected: Vec<&str> = source
+        .lines()
+        .skip(start - 1)
+        .take(end + 1 - start)
+        .collect();
+    dedent(&selected)
+}
+
+// Find `fn name`, `struct name` or `impl ... name` and keep it up to the matching brace
+pub fn extract_item(source: &str, name: &str) -> Option<String> {
+    let all: Vec<&str> = source.lines().collect();
+    let start = all
+        .iter()
+        .position(|line| declares(line.trim_start(), name))?;
+
+    let mut depth = 0;
+    let mut opened = false;
+    for (index, line) in all.iter().enumerate().skip(start) {
+        for c in line.chars() {
+            match c {
+                '{' => {
+                    depth += 1;
+                    opened = true;
+                }
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        let ended = (opened && depth == 0) || (!opened && line.trim_end().ends_with(';'));
+        if ended {
+            return Some(dedent(&all[start..=index]));
+        }
+    }
+    None
+}
+
+fn declares(line: &str, name: &str) -> bool {
+    let line = line.strip_prefix("pub ").unwrap_or(line);
+    let is_name = |rest: &str| {
+        rest.strip_prefix(name)
+            .and_then(|after| after.chars().next())
+            .is_some_and(|c| !c.is_alphanumeric() && c != '_')
+    };
+    ["fn ", "struct ", "enum ", "trait "]
+        .iter()
+        .any(|keyword| line.strip_prefix(keyword).is_some_and(is_name))
+        || (line.starts_with("impl") && line.split_whitespace().any(|word| word == name))
+}
+
+fn dedent(lines: &[&str]) -> String {
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "use std::fmt;
+
+pub struct Foo();
+
+impl Foo {
+    fn bar(&self) -> u32 {
+        if true {
+            1
+        } else {
+            2
+        }
+    }
+
+    fn barbaz(&self) {}
+}
+";
+
+    mod extract_item {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn extract_method() {
+            let item = extract_item(SOURCE, "bar").unwrap();
+
+            assert_eq!(
+                item,
+                "fn bar(&self) -> u32 {\n    if true {\n        1\n    } else {\n        2\n    }\n}"
+            );
+        }
+
+        #[test]
+        fn extract_struct() {
+            assert_eq!(
+                extract_item(SOURCE, "Foo"),
+                Some("pub struct Foo();".into())
+            );
+        }
+
+        #[test]
+        fn do_not_match_prefix() {
+            assert_eq!(
+                extract_item(SOURCE, "barbaz"),
+                Some("fn barbaz(&self) {}".into())
+            );
+            assert_eq!(extract_item(SOURCE, "ba"), None);
+        }
+    }
+
+    mod expand {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn keep_plain_markdown() {
+            let context = Context {
+                base: PathBuf::from("."),
+                criterion: PathBuf::from("."),
+            };
+
+            let result = expand("# Title\n\n- item", &context).unwrap();
+
+            assert_eq!(result, "# Title\n\n- item\n");
+        }
+
+        #[test]
+        fn include_solver_function() {
+            let context = Context {
+                base: PathBuf::from(env!("CARGO_MANIFEST_DIR")),
+                criterion: PathBuf::from("."),
+            };
+
+            let result = expand(
+                "{{#include ../waterpouring-imp/src/imp.rs#solve_with}}",
+                &context,
+            )
+            .unwrap();
+
+            assert!(result.starts_with("```rust\nfn solve_with(&self, problem: Problem"));
+            assert!(result.ends_with("}\n```\n"));
+        }
+
+        #[test]
+        fn hyperfine_table() {
+            let context = Context {
+                base: PathBuf::from(env!("CARGO_MANIFEST_DIR")),
+                criterion: PathBuf::from("."),
+            };
+
+            // compared with itself, every solver is unchanged
+            let result = expand(
+                "{{#hyperfine ../results/perf.json ../results/perf.json}}",
+                &context,
+            )
+            .unwrap();
+
+            assert!(result.starts_with("| Solver | Mean (ms)"));
+            assert!(result.contains("| rec2 |"));
+            assert!(result.contains("| Baseline (ms) |"));
+            assert_eq!(result.matches("| unchanged |").count(), 3);
+        }
+
+        #[test]
+        fn svg_of_a_solution() {
+            let context = Context {
+                base: PathBuf::from("."),
+                criterion: PathBuf::from("."),
+            };
+
+            let animated = expand("{{#svg 0/5, 0/3 -> 4/5, 0/3}}", &context).unwrap();
+            let filmstrip = expand("{{#filmstrip 0/5, 0/3 -> 4/5, 0/3}}", &context).unwrap();
+
+            assert!(animated.starts_with("<div class=\"svg\">\n<svg "));
+            assert!(animated.ends_with("</svg>\n</div>\n"));
+            assert!(!animated.contains("\n\n"));
+            assert!(animated.contains("<animate "));
+            assert!(!filmstrip.contains("<animate "));
+            assert!(expand("{{#svg 0/5, 0/3}}", &context).is_err());
+        }
+
+        #[test]
+        fn unknown_directive() {
+            let context = Context {
+                base: PathBuf::from("."),
+                criterion: PathBuf::from("."),
+            };
+
+            assert!(expand("{{#plop}}", &context).is_err());
+        }
+    }
+}
@@ -0,0 +1,7 @@
+#[macro_use]
+extern crate serde_derive;
+
+pub mod criterion;
+pub mod error;
+pub mod include;
+pub mod slides;
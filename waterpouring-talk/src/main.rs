@@ -1,5 +1,50 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
 
+use waterpouring_bench::alloc::criterion_dir;
+use waterpouring_talk::error::{TalkError, TalkResult};
+use waterpouring_talk::include::{expand, read, Context};
+use waterpouring_talk::slides::Renderer;
+
+fn generate(input: &Path, output: &Path, criterion: &Path) -> TalkResult<()> {
+    let markdown = read(input)?;
+    let context = Context {
+        base: input.parent().unwrap_or_else(|| Path::new(".")).into(),
+        criterion: criterion.into(),
+    };
+    let html = Renderer::default().render(&expand(&markdown, &context)?);
+
+    let io_error = |source| TalkError::Io {
+        path: output.into(),
+        source,
+    };
+    if let Some(dir) = output.parent() {
+        fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    fs::write(output, html).map_err(io_error)
+}
 
 fn main() {
-    println!("Todo")
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target = manifest.join("../target");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let input = args
+        .first()
+        .map_or_else(|| manifest.join("notes.md"), PathBuf::from);
+    let output = args
+        .get(1)
+        .map_or_else(|| target.join("talk/index.html"), PathBuf::from);
+
+    // where `cargo bench` writes criterion results, `CARGO_TARGET_DIR` included
+    let criterion = criterion_dir();
+
+    match generate(&input, &output, &criterion) {
+        Ok(()) => println!("Slides written to {}", output.display()),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
 }
@@ -0,0 +1,18 @@
+const slides = document.querySelectorAll('.slide');
+let current = Math.min(Number(location.hash.slice(1)) || 0, slides.length - 1);
+
+function show(index) {
+    current = Math.max(0, Math.min(index, slides.length - 1));
+    slides.forEach((slide, i) => slide.classList.toggle('current', i === current));
+    location.hash = current;
+}
+
+document.addEventListener('keydown', event => {
+    if (['ArrowRight', 'PageDown', ' '].includes(event.key)) {
+        show(current + 1);
+    } else if (['ArrowLeft', 'PageUp'].includes(event.key)) {
+        show(current - 1);
+    }
+});
+
+show(current);
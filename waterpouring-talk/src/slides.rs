@@ -0,0 +1,180 @@
+use pulldown_cmark::{html, CodeBlockKind, Event, Options, Parser, Tag};
+use syntect::highlighting::ThemeSet;
+use syntect::html::highlighted_html_for_string;
+use syntect::parsing::SyntaxSet;
+
+const STYLE: &str = include_str!("slides.css");
+const SCRIPT: &str = include_str!("slides.js");
+
+pub struct Renderer {
+    syntaxes: SyntaxSet,
+    themes: ThemeSet,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self {
+            syntaxes: SyntaxSet::load_defaults_newlines(),
+            themes: ThemeSet::load_defaults(),
+        }
+    }
+}
+
+impl Renderer {
+    pub fn render(&self, markdown: &str) -> String {
+        let slides: Vec<String> = split(markdown)
+            .iter()
+            .map(|slide| {
+                format!(
+                    "<section class=\"slide\">\n{}</section>\n",
+                    self.to_html(slide)
+                )
+            })
+            .collect();
+        let title = escape(title(markdown).unwrap_or("Slides"));
+
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n{}<script>\n{}</script>\n</body>\n</html>\n",
+            title,
+            STYLE,
+            slides.concat(),
+            SCRIPT
+        )
+    }
+
+    // Markdown to HTML, with fenced code blocks highlighted by syntect
+    pub fn to_html(&self, markdown: &str) -> String {
+        let mut events: Vec<Event> = vec![];
+        let mut code: Option<(String, String)> = None;
+        for event in Parser::new_ext(markdown, Options::ENABLE_TABLES) {
+            match (event, &mut code) {
+                (Event::Start(Tag::CodeBlock(kind)), _) => {
+                    let lang = match kind {
+                        CodeBlockKind::Fenced(lang) => lang.to_string(),
+                        CodeBlockKind::Indented => String::new(),
+                    };
+                    code = Some((lang, String::new()));
+                }
+                (Event::Text(text), Some((_, content))) => content.push_str(&text),
+                (Event::End(Tag::CodeBlock(_)), Some((lang, content))) => {
+                    events.push(Event::Html(self.highlight(lang, content).into()));
+                    code = None;
+                }
+                (event, _) => events.push(event),
+            }
+        }
+
+        let mut result = String::new();
+        html::push_html(&mut result, events.into_iter());
+        result
+    }
+
+    fn highlight(&self, lang: &str, code: &str) -> String {
+        let syntax = self
+            .syntaxes
+            .find_syntax_by_token(lang)
+            .unwrap_or_else(|| self.syntaxes.find_syntax_plain_text());
+        let theme = &self.themes.themes["InspiredGitHub"];
+        highlighted_html_for_string(code, &self.syntaxes, syntax, theme)
+            .unwrap_or_else(|_| format!("<pre>{}</pre>\n", escape(code)))
+    }
+}
+
+// A new slide starts at each level 1 or 2 heading outside code blocks
+pub fn split(markdown: &str) -> Vec<String> {
+    let mut slides: Vec<String> = vec![];
+    let mut current = String::new();
+    let mut in_code = false;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        }
+        let heading = !in_code && (line.starts_with("# ") || line.starts_with("## "));
+        if heading && !current.trim().is_empty() {
+            slides.push(current);
+            current = String::new();
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+    if !current.trim().is_empty() {
+        slides.push(current);
+    }
+
+    slides
+}
+
+fn title(markdown: &str) -> Option<&str> {
+    markdown
+        .lines()
+        .find_map(|line| line.strip_prefix("# "))
+        .map(str::trim)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod split {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn one_slide_per_heading() {
+            let slides = split("# Title\n\n## First\n- a\n\n## Second\n- b\n");
+
+            assert_eq!(
+                slides,
+                vec!["# Title\n\n", "## First\n- a\n\n", "## Second\n- b\n"]
+            );
+        }
+
+        #[test]
+        fn ignore_headings_in_code() {
+            let slides = split("## Code\n```sh\n# comment\n```\n");
+
+            assert_eq!(slides.len(), 1);
+        }
+    }
+
+    mod render {
+        use super::*;
+
+        #[test]
+        fn highlight_rust_code() {
+            let html = Renderer::default().to_html("```rust\nfn main() {}\n```\n");
+
+            assert!(html.starts_with("<pre style="));
+            assert!(html.contains("main"));
+        }
+
+        #[test]
+        fn render_tables() {
+            let html = Renderer::default().to_html("| a | b |\n|---|---|\n| 1 | 2 |\n");
+
+            assert!(html.contains("<table>"));
+        }
+
+        #[test]
+        fn render_page() {
+            let html = Renderer::default().render("# Talk\n\n## Intro\n\nHello\n");
+
+            assert!(html.contains("<title>Talk</title>"));
+            assert_eq!(html.matches("<section class=\"slide\">").count(), 2);
+        }
+
+        #[test]
+        fn escape_title() {
+            let html = Renderer::default().render("# Rust <3 & C\n");
+
+            assert!(html.contains("<title>Rust &lt;3 &amp; C</title>"));
+        }
+    }
+}
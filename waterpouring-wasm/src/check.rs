@@ -0,0 +1,51 @@
+use waterpouring_model::problem::{Issue, Validation};
+
+use crate::error::WasmError;
+
+#[derive(Serialize)]
+pub(crate) struct WasmIssue {
+    severity: String,
+    code: String,
+    message: String,
+}
+
+impl WasmIssue {
+    fn new(issue: &Issue) -> Self {
+        let (severity, message) = match issue {
+            Issue::Error(reason) => ("error", reason.to_string()),
+            Issue::Warning(warning) => ("warning", warning.to_string()),
+        };
+
+        Self {
+            severity: severity.to_string(),
+            code: issue.code().to_string(),
+            message,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub(crate) struct WasmCheck {
+    valid: bool,
+    // only for inputs that cannot be parsed
+    error: Option<WasmError>,
+    issues: Vec<WasmIssue>,
+}
+
+impl WasmCheck {
+    pub(crate) fn new(validation: &Validation) -> Self {
+        Self {
+            valid: validation.is_valid(),
+            error: None,
+            issues: validation.issues.iter().map(WasmIssue::new).collect(),
+        }
+    }
+
+    pub(crate) fn error(error: WasmError) -> Self {
+        Self {
+            valid: false,
+            error: Some(error),
+            issues: vec![],
+        }
+    }
+}
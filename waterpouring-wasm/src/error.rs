@@ -0,0 +1,95 @@
+use waterpouring_model::labels::Labels;
+use waterpouring_model::parse::ParseError;
+use waterpouring_model::plan::PlanError;
+use waterpouring_model::problem::ProblemParseError;
+use waterpouring_model::solver::SolverError;
+use waterpouring_model::solver::SolverError::{InvalidProblem, LimitExceeded, UnsolvableProblem};
+
+// Where a parse error occurs, `input` is "from", "to" or "state"
+#[derive(Clone, Serialize)]
+pub(crate) struct WasmPosition {
+    input: String,
+    offset: usize,
+}
+
+#[derive(Clone, Serialize)]
+pub(crate) struct WasmError {
+    code: String,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<WasmPosition>,
+}
+
+impl WasmError {
+    fn new(code: &str, message: String) -> Self {
+        Self {
+            code: code.to_string(),
+            message,
+            position: None,
+        }
+    }
+
+    pub(crate) fn parse(input: &str, error: &ParseError) -> Self {
+        Self {
+            position: error.position.map(|offset| WasmPosition {
+                input: input.to_string(),
+                offset,
+            }),
+            ..Self::new(
+                error.code(),
+                format!("Invalid '{}' state, {}", input, error),
+            )
+        }
+    }
+
+    // Errors between both states, e.g. conflicting glass names, have no position
+    pub(crate) fn problem(error: &ProblemParseError) -> Self {
+        match error.input {
+            Some(input) => Self::parse(input.name(), &error.error),
+            None => Self::new(error.code(), error.to_string()),
+        }
+    }
+
+    pub(crate) fn solver(error: &SolverError) -> Self {
+        let message = match error {
+            InvalidProblem { reason, .. } => format!("Cannot solve because {}", reason),
+            UnsolvableProblem { .. } => "No solution found!".to_string(),
+            LimitExceeded { .. } => error.to_string(),
+        };
+
+        Self::new(error.code(), message)
+    }
+
+    pub(crate) fn unknown_solver(name: &str) -> Self {
+        Self::new("UNKNOWN_SOLVER", format!("Unknown solver '{}'", name))
+    }
+
+    pub(crate) fn invalid_options(error: &serde_wasm_bindgen::Error) -> Self {
+        Self::new("INVALID_OPTIONS", format!("Invalid options, {}", error))
+    }
+
+    pub(crate) fn invalid_operation(reason: &str) -> Self {
+        Self::new(
+            "INVALID_OPERATION",
+            format!("Invalid operation, {}", reason),
+        )
+    }
+
+    pub(crate) fn invalid_move(error: &PlanError, labels: &Labels) -> Self {
+        Self::new(
+            error.code(),
+            format!("Invalid move, {}", error.describe(labels)),
+        )
+    }
+
+    pub(crate) fn invalid_save(error: &serde_wasm_bindgen::Error) -> Self {
+        Self::new("INVALID_SAVE", format!("Invalid save, {}", error))
+    }
+
+    pub(crate) fn invalid_solution(error: &PlanError, labels: &Labels) -> Self {
+        Self::new(
+            "INVALID_SOLUTION",
+            format!("Invalid solution, {}", error.describe(labels)),
+        )
+    }
+}
@@ -0,0 +1,145 @@
+use wasm_bindgen::prelude::*;
+
+use waterpouring_model::game::Game;
+use waterpouring_model::operations::Operation;
+use waterpouring_solvers::registry;
+
+use crate::error::WasmError;
+use crate::operation::WasmOperation;
+use crate::{parse_problem, to_js};
+
+// What is needed to restore a game
+#[derive(Serialize, Deserialize)]
+struct WasmSave {
+    from: String,
+    to: String,
+    moves: Vec<WasmOperation>,
+}
+
+// Errors are thrown as `WasmError` objects
+#[wasm_bindgen(js_name = Game)]
+pub struct WasmGame {
+    game: Game,
+}
+
+fn operation_to_js(operation: Option<Operation>) -> JsValue {
+    to_js(&operation.as_ref().map(WasmOperation::new))
+}
+
+fn from_js_operation(operation: JsValue) -> Result<Operation, JsValue> {
+    let operation: WasmOperation = serde_wasm_bindgen::from_value(operation)
+        .map_err(|error| to_js(&WasmError::invalid_operation(&error.to_string())))?;
+    operation.to_operation().map_err(|error| to_js(&error))
+}
+
+#[wasm_bindgen(js_class = Game)]
+impl WasmGame {
+    #[wasm_bindgen(constructor)]
+    pub fn new(from: &str, to: &str) -> Result<WasmGame, JsValue> {
+        let problem = parse_problem(from, to).map_err(|error| to_js(&error))?;
+
+        Ok(Self {
+            game: Game::new(problem),
+        })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn state(&self) -> String {
+        self.game.problem().labels.state(self.game.state())
+    }
+
+    #[wasm_bindgen(js_name = availableOperations, unchecked_return_type = "WasmOperation[]")]
+    pub fn available_operations(&self) -> JsValue {
+        let operations: Vec<WasmOperation> = self
+            .game
+            .available_operations()
+            .iter()
+            .map(WasmOperation::new)
+            .collect();
+        to_js(&operations)
+    }
+
+    // Give the new state
+    pub fn apply(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "WasmOperation")] operation: JsValue,
+    ) -> Result<String, JsValue> {
+        let operation = from_js_operation(operation)?;
+        let labels = self.game.problem().labels.clone();
+        self.game
+            .apply(operation)
+            .map(|state| labels.state(state))
+            .map_err(|error| to_js(&WasmError::invalid_move(&error, &labels)))
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "WasmOperation | null")]
+    pub fn undo(&mut self) -> JsValue {
+        operation_to_js(self.game.undo())
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "WasmOperation | null")]
+    pub fn redo(&mut self) -> JsValue {
+        operation_to_js(self.game.redo())
+    }
+
+    #[wasm_bindgen(js_name = canUndo)]
+    pub fn can_undo(&self) -> bool {
+        self.game.can_undo()
+    }
+
+    #[wasm_bindgen(js_name = canRedo)]
+    pub fn can_redo(&self) -> bool {
+        self.game.can_redo()
+    }
+
+    #[wasm_bindgen(js_name = isSolved)]
+    pub fn is_solved(&self) -> bool {
+        self.game.is_solved()
+    }
+
+    // Next optimal move, `null` when solved, the default solver is used without name
+    #[wasm_bindgen(unchecked_return_type = "WasmOperation | null")]
+    pub fn hint(&self, solver: Option<String>) -> Result<JsValue, JsValue> {
+        let entry = match &solver {
+            None => registry::default_solver(),
+            Some(name) => {
+                registry::find(name).ok_or_else(|| to_js(&WasmError::unknown_solver(name)))?
+            }
+        };
+        let operation = self
+            .game
+            .hint(entry.create().as_ref())
+            .map_err(|error| to_js(&WasmError::solver(&error)))?;
+
+        Ok(operation_to_js(operation))
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "WasmSave")]
+    pub fn save(&self) -> JsValue {
+        let problem = self.game.problem();
+        to_js(&WasmSave {
+            from: problem.labels.state(&problem.from),
+            to: problem.labels.state(&problem.to),
+            moves: self.game.moves().iter().map(WasmOperation::new).collect(),
+        })
+    }
+
+    pub fn load(
+        #[wasm_bindgen(unchecked_param_type = "WasmSave")] save: JsValue,
+    ) -> Result<WasmGame, JsValue> {
+        let save: WasmSave = serde_wasm_bindgen::from_value(save)
+            .map_err(|error| to_js(&WasmError::invalid_save(&error)))?;
+        let problem = parse_problem(&save.from, &save.to).map_err(|error| to_js(&error))?;
+        let moves = save
+            .moves
+            .iter()
+            .map(WasmOperation::to_operation)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|error| to_js(&error))?;
+        let labels = problem.labels.clone();
+        let game = Game::replay(problem, &moves)
+            .map_err(|error| to_js(&WasmError::invalid_move(&error, &labels)))?;
+
+        Ok(Self { game })
+    }
+}
@@ -1,21 +1,37 @@
+use serde::Serialize;
 use wasm_bindgen::prelude::*;
 
 #[macro_use]
 extern crate serde_derive;
 
 use waterpouring_model::operations::Operation;
-use waterpouring_model::problem::Problem;
-use waterpouring_model::solver::SolverError::{InvalidProblem, UnsolvableProblem};
-use waterpouring_model::solver::{Solver, SolverError};
-use waterpouring_model::state::State;
-use waterpouring_rec::rec::RecSolver;
+use waterpouring_model::plan::Plan;
+use waterpouring_model::problem::{validate, Problem};
+use waterpouring_model::solver::SolverError;
+use waterpouring_solvers::registry;
 
-use crate::result::WasmResult;
+use crate::check::WasmCheck;
+use crate::error::WasmError;
+use crate::options::WasmOptions;
+use crate::result::{WasmResult, WasmStats};
+use crate::solver::WasmSolverInfo;
 use crate::step::WasmStep;
 
+pub use crate::game::WasmGame;
+pub use crate::search::WasmSearch;
+pub use crate::svg::{svg_of_solution, svg_of_state};
+
+mod check;
+mod error;
+mod game;
 mod operation;
+mod options;
 mod result;
+mod search;
+mod solver;
 mod step;
+mod svg;
+mod types;
 
 //When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 //allocator.
@@ -23,33 +39,113 @@ mod step;
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
-#[wasm_bindgen]
+// Log panics in the browser console instead of an opaque `unreachable`
+#[wasm_bindgen(start)]
+pub fn start() {
+    console_error_panic_hook::set_once();
+}
+
+#[wasm_bindgen(unchecked_return_type = "WasmResult")]
 pub fn solve(from: &str, to: &str) -> JsValue {
-    let problem = Problem::from((from, to));
-    let solver = RecSolver();
+    let problem = match parse_problem(from, to) {
+        Ok(problem) => problem,
+        Err(error) => return to_js(&WasmResult::error(error)),
+    };
+    let solver = registry::default_solver().create();
 
-    let result = transform(&problem.from.clone(), solver.solve(problem));
+    let result = transform(&problem, solver.solve(problem.clone()));
 
-    JsValue::from_serde(&result).unwrap()
+    to_js(&result)
 }
 
-fn transform(from: &State, result: Result<Vec<Operation>, SolverError>) -> WasmResult {
-    match result {
-        Err(InvalidProblem { reason, .. }) => {
-            let err = format!("Cannot solve because {}", reason);
-            WasmResult::error(err)
-        }
-        Err(UnsolvableProblem { .. }) => WasmResult::error("No solution found!".into()),
-        Ok(moves) => {
-            let mut steps: Vec<WasmStep> = vec![];
-            steps.push(WasmStep::init(from));
-            let mut state = from.clone();
-            for m in moves {
-                let (step, next) = WasmStep::step(&state, &m);
-                steps.push(step);
-                state = next;
+// options: `{ solver?: string, maxDepth?: number, maxStates?: number, stats?: boolean }`
+#[wasm_bindgen(js_name = solveWith, unchecked_return_type = "WasmResult")]
+pub fn solve_with(
+    from: &str,
+    to: &str,
+    #[wasm_bindgen(unchecked_param_type = "SolveOptions | undefined")] options: JsValue,
+) -> JsValue {
+    let options = match WasmOptions::from_js(options) {
+        Ok(options) => options,
+        Err(error) => return to_js(&WasmResult::error(error)),
+    };
+    let entry = match &options.solver {
+        None => registry::default_solver(),
+        Some(name) => match registry::find(name) {
+            Some(entry) => entry,
+            None => return to_js(&WasmResult::error(WasmError::unknown_solver(name))),
+        },
+    };
+
+    let problem = match parse_problem(from, to) {
+        Ok(problem) => problem,
+        Err(error) => return to_js(&WasmResult::error(error)),
+    };
+    let solver = entry.create();
+    let result = match solver.solve_with(problem.clone(), &options.limits()) {
+        Err(error) => transform(&problem, Err(error)),
+        Ok(solution) => {
+            let result = transform(&problem, Ok(solution.operations));
+            if options.stats {
+                result.with_stats(WasmStats::new(&solution.stats))
+            } else {
+                result
             }
-            WasmResult::solved(steps)
         }
+    };
+
+    to_js(&result)
+}
+
+#[wasm_bindgen(js_name = listSolvers, unchecked_return_type = "WasmSolverInfo[]")]
+pub fn list_solvers() -> JsValue {
+    let default = registry::default_solver().info.name;
+    let infos: Vec<WasmSolverInfo> = registry::solvers()
+        .iter()
+        .map(|entry| WasmSolverInfo::new(&entry.info, entry.info.name == default))
+        .collect();
+
+    to_js(&infos)
+}
+
+#[wasm_bindgen(unchecked_return_type = "WasmCheck")]
+pub fn check(from: &str, to: &str) -> JsValue {
+    let check = match parse_problem(from, to) {
+        Ok(problem) => WasmCheck::new(&validate(&problem)),
+        Err(error) => WasmCheck::error(error),
+    };
+
+    to_js(&check)
+}
+
+// Glasses can be named, with a unit and decimal volumes, e.g. `jug:0/5L, cup:0/0.5L`
+pub(crate) fn parse_problem(from: &str, to: &str) -> Result<Problem, WasmError> {
+    Problem::parse(from, to).map_err(|error| WasmError::problem(&error))
+}
+
+// Serialization only fails on unsupported types, the message is returned instead of a panic
+pub(crate) fn to_js<T: Serialize>(value: &T) -> JsValue {
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    value
+        .serialize(&serializer)
+        .unwrap_or_else(|error| JsValue::from_str(&error.to_string()))
+}
+
+pub(crate) fn transform(
+    problem: &Problem,
+    result: Result<Vec<Operation>, SolverError>,
+) -> WasmResult {
+    match result {
+        Err(error) => WasmResult::error(WasmError::solver(&error)),
+        Ok(moves) => match Plan::new(problem.clone(), moves).validate() {
+            Err(error) => WasmResult::error(WasmError::invalid_solution(&error, &problem.labels)),
+            Ok(trace) => {
+                let mut steps: Vec<WasmStep> = vec![];
+                let labels = trace.labels();
+                steps.push(WasmStep::init(trace.start(), labels));
+                steps.extend(trace.iter().map(|step| WasmStep::step(step, labels)));
+                WasmResult::solved(steps)
+            }
+        },
     }
 }
@@ -1,20 +1,77 @@
 use waterpouring_model::operations::Operation;
 use waterpouring_model::operations::Operation::{Empty, Fill, Pour};
 
-#[derive(Serialize)]
+use crate::error::WasmError;
+
+// `kind` tells the operation, `from` and `to` are kept for older clients
+#[derive(Serialize, Deserialize)]
 pub(crate) struct WasmOperation {
+    kind: String,
     from: Option<usize>,
     to: Option<usize>,
 }
 
 impl WasmOperation {
     pub(crate) fn new(ope: &Operation) -> Self {
-        let (from, to) = match ope {
-            Empty { glass: i } => (Some(*i), None),
-            Fill { glass: i } => (None, Some(*i)),
-            Pour { from: f, to: t } => (Some(*f), Some(*t)),
+        let (kind, from, to) = match ope {
+            Empty { glass: i } => ("empty", Some(*i), None),
+            Fill { glass: i } => ("fill", None, Some(*i)),
+            Pour { from: f, to: t } => ("pour", Some(*f), Some(*t)),
         };
 
-        Self { from, to }
+        Self {
+            kind: kind.to_string(),
+            from,
+            to,
+        }
+    }
+
+    pub(crate) fn to_operation(&self) -> Result<Operation, WasmError> {
+        match (self.kind.as_str(), self.from, self.to) {
+            ("empty", Some(glass), _) => Ok(Operation::empty(glass)),
+            ("fill", _, Some(glass)) => Ok(Operation::fill(glass)),
+            ("pour", Some(from), Some(to)) => Ok(Operation::pour(from, to)),
+            ("empty", ..) | ("fill", ..) | ("pour", ..) => Err(WasmError::invalid_operation(
+                &format!("missing glass for '{}'", self.kind),
+            )),
+            _ => Err(WasmError::invalid_operation(&format!(
+                "unknown kind '{}'",
+                self.kind
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn kind_of_operations() {
+        let kinds: Vec<String> = [
+            Operation::empty(0),
+            Operation::fill(1),
+            Operation::pour(0, 1),
+        ]
+        .iter()
+        .map(|op| WasmOperation::new(op).kind)
+        .collect();
+
+        assert_eq!(kinds, vec!["empty", "fill", "pour"]);
+    }
+
+    #[test]
+    fn back_to_operation() {
+        for op in &[
+            Operation::empty(0),
+            Operation::fill(1),
+            Operation::pour(0, 1),
+        ] {
+            let result = WasmOperation::new(op).to_operation().ok();
+
+            assert_eq!(result, Some(*op));
+        }
     }
 }
@@ -0,0 +1,32 @@
+use wasm_bindgen::JsValue;
+use waterpouring_model::solver::Limits;
+
+use crate::error::WasmError;
+
+// Options given by JS, every field is optional
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct WasmOptions {
+    pub(crate) solver: Option<String>,
+    pub(crate) max_depth: Option<usize>,
+    pub(crate) max_states: Option<usize>,
+    pub(crate) stats: bool,
+}
+
+impl WasmOptions {
+    // `undefined` or `null` give the default options
+    pub(crate) fn from_js(options: JsValue) -> Result<Self, WasmError> {
+        if options.is_undefined() || options.is_null() {
+            return Ok(Self::default());
+        }
+        serde_wasm_bindgen::from_value(options).map_err(|error| WasmError::invalid_options(&error))
+    }
+
+    pub(crate) fn limits(&self) -> Limits {
+        Limits {
+            max_depth: self.max_depth,
+            max_states: self.max_states,
+            ..Limits::default()
+        }
+    }
+}
@@ -1,20 +1,52 @@
+use waterpouring_model::solver::Stats;
+
+use crate::error::WasmError;
 use crate::step::WasmStep;
 
+#[derive(Serialize)]
+pub(crate) struct WasmStats {
+    depth: usize,
+    visited: usize,
+}
+
+impl WasmStats {
+    pub(crate) fn new(stats: &Stats) -> Self {
+        Self {
+            depth: stats.depth,
+            visited: stats.visited,
+        }
+    }
+}
+
 #[derive(Serialize)]
 pub(crate) struct WasmResult {
-    error: Option<String>,
+    error: Option<WasmError>,
     steps: Vec<WasmStep>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<WasmStats>,
 }
 
 impl WasmResult {
-    pub(crate) fn error(str: String) -> Self {
+    pub(crate) fn error(error: WasmError) -> Self {
         Self {
-            error: Some(str),
+            error: Some(error),
             steps: vec![],
+            stats: None,
         }
     }
 
     pub(crate) fn solved(steps: Vec<WasmStep>) -> Self {
-        Self { error: None, steps }
+        Self {
+            error: None,
+            steps,
+            stats: None,
+        }
+    }
+
+    pub(crate) fn with_stats(self, stats: WasmStats) -> Self {
+        Self {
+            stats: Some(stats),
+            ..self
+        }
     }
 }
@@ -0,0 +1,113 @@
+use wasm_bindgen::prelude::*;
+
+use waterpouring_model::problem::Problem;
+use waterpouring_model::search::{Progress, Search, SearchStatus};
+
+use crate::error::WasmError;
+use crate::options::WasmOptions;
+use crate::result::{WasmResult, WasmStats};
+use crate::{parse_problem, to_js, transform};
+
+#[derive(Serialize)]
+struct WasmProgress {
+    depth: usize,
+    visited: usize,
+    frontier: usize,
+}
+
+impl WasmProgress {
+    fn new(progress: &Progress) -> Self {
+        Self {
+            depth: progress.depth,
+            visited: progress.visited,
+            frontier: progress.frontier,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct WasmSearchStep {
+    done: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    progress: Option<WasmProgress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<WasmResult>,
+}
+
+// A search driven from JS, e.g. with `requestAnimationFrame`, so the page stays responsive
+#[wasm_bindgen(js_name = Search)]
+pub struct WasmSearch {
+    search: Result<(Problem, Search), WasmError>,
+    stats: bool,
+}
+
+#[wasm_bindgen(js_class = Search)]
+impl WasmSearch {
+    // options: same as `solveWith`, the solver name is ignored
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        from: &str,
+        to: &str,
+        #[wasm_bindgen(unchecked_param_type = "SolveOptions | undefined")] options: JsValue,
+    ) -> Self {
+        let options = WasmOptions::from_js(options);
+        let stats = options.as_ref().is_ok_and(|options| options.stats);
+        let search = parse_problem(from, to).and_then(|problem| {
+            let options = options?;
+            let search = Search::new(problem.clone(), options.limits());
+            Ok((problem, search))
+        });
+
+        Self { search, stats }
+    }
+
+    // Expand at most `budget` states, give `{ done: false, progress }` or `{ done: true, result }`
+    #[wasm_bindgen(unchecked_return_type = "WasmSearchStep")]
+    pub fn step(&mut self, budget: u32) -> JsValue {
+        let (problem, search) = match &mut self.search {
+            Ok((problem, search)) => (&*problem, search),
+            Err(error) => {
+                return to_js(&WasmSearchStep {
+                    done: true,
+                    progress: None,
+                    result: Some(WasmResult::error(error.clone())),
+                })
+            }
+        };
+
+        let step = match search.step(budget as usize) {
+            SearchStatus::Running(progress) => WasmSearchStep {
+                done: false,
+                progress: Some(WasmProgress::new(&progress)),
+                result: None,
+            },
+            SearchStatus::Done(Err(error)) => WasmSearchStep {
+                done: true,
+                progress: None,
+                result: Some(transform(problem, Err(error))),
+            },
+            SearchStatus::Done(Ok(solution)) => {
+                let result = transform(problem, Ok(solution.operations));
+                let result = if self.stats {
+                    result.with_stats(WasmStats::new(&solution.stats))
+                } else {
+                    result
+                };
+                WasmSearchStep {
+                    done: true,
+                    progress: None,
+                    result: Some(result),
+                }
+            }
+        };
+
+        to_js(&step)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn done(&self) -> bool {
+        self.search
+            .as_ref()
+            .map_or(true, |(_, search)| search.is_done())
+    }
+}
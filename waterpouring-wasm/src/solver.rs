@@ -0,0 +1,24 @@
+use waterpouring_solvers::registry::SolverInfo;
+
+#[derive(Serialize)]
+pub(crate) struct WasmSolverInfo {
+    name: String,
+    description: String,
+    optimal: bool,
+    memory: String,
+    features: Vec<String>,
+    default: bool,
+}
+
+impl WasmSolverInfo {
+    pub(crate) fn new(info: &SolverInfo, default: bool) -> Self {
+        Self {
+            name: info.name.to_string(),
+            description: info.description.to_string(),
+            optimal: info.optimal,
+            memory: format!("{:?}", info.memory),
+            features: info.features.iter().map(|f| format!("{:?}", f)).collect(),
+            default,
+        }
+    }
+}
@@ -1,37 +1,41 @@
-use waterpouring_model::operations::Operation;
+use waterpouring_model::labels::Labels;
+use waterpouring_model::plan::Step;
 use waterpouring_model::state::State;
 
 use crate::operation::WasmOperation;
 
+// States and `label` use the glass names and unit of the problem
 #[derive(Serialize)]
 pub(crate) struct WasmStep {
     from: String,
     operation: Option<WasmOperation>,
+    label: Option<String>,
     to: Option<String>,
 }
 
 impl WasmStep {
-    pub(crate) fn init(state: &State) -> Self {
-        let from: String = format!("{}", state);
+    pub(crate) fn init(state: &State, labels: &Labels) -> Self {
+        let from: String = labels.state(state);
 
         Self {
             from,
             operation: None,
+            label: None,
             to: None,
         }
     }
 
-    pub(crate) fn step(state: &State, ope: &Operation) -> (Self, State) {
-        let from: String = format!("{}", state);
-        let operation = Some(WasmOperation::new(ope));
-        let next = state.apply(*ope);
-        let to: Option<String> = Some(format!("{}", next));
-        let step = Self {
+    pub(crate) fn step(step: &Step, labels: &Labels) -> Self {
+        let from: String = labels.state(&step.before);
+        let operation = Some(WasmOperation::new(&step.operation));
+        let label = Some(labels.operation(&step.operation));
+        let to: Option<String> = Some(labels.state(&step.after));
+
+        Self {
             from,
             operation,
+            label,
             to,
-        };
-
-        (step, next)
+        }
     }
 }
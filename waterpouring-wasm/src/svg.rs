@@ -0,0 +1,39 @@
+use wasm_bindgen::prelude::*;
+
+use waterpouring_model::parse::parse_labelled;
+use waterpouring_model::plan::Plan;
+use waterpouring_model::svg::{animated_svg, filmstrip_svg, labelled_state_svg, SvgOptions};
+use waterpouring_solvers::registry;
+
+use crate::error::WasmError;
+use crate::{parse_problem, to_js};
+
+// SVG documents are returned as strings, errors are thrown as `WasmError` objects
+#[wasm_bindgen(js_name = stateSvg)]
+pub fn svg_of_state(state: &str) -> Result<String, JsValue> {
+    let (state, labels) =
+        parse_labelled(state).map_err(|error| to_js(&WasmError::parse("state", &error)))?;
+
+    Ok(labelled_state_svg(&state, &labels, &SvgOptions::default()))
+}
+
+// Animated with SMIL, unless `animated` is false: the steps are then side by side
+#[wasm_bindgen(js_name = solutionSvg)]
+pub fn svg_of_solution(from: &str, to: &str, animated: Option<bool>) -> Result<String, JsValue> {
+    let problem = parse_problem(from, to).map_err(|error| to_js(&error))?;
+    let solver = registry::default_solver().create();
+    let operations = solver
+        .solve(problem.clone())
+        .map_err(|error| to_js(&WasmError::solver(&error)))?;
+    let labels = problem.labels.clone();
+    let trace = Plan::new(problem, operations)
+        .validate()
+        .map_err(|error| to_js(&WasmError::invalid_solution(&error, &labels)))?;
+
+    let options = SvgOptions::default();
+    if animated.unwrap_or(true) {
+        Ok(animated_svg(&trace, &options))
+    } else {
+        Ok(filmstrip_svg(&trace, &options))
+    }
+}
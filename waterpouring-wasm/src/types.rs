@@ -0,0 +1,83 @@
+use wasm_bindgen::prelude::*;
+
+// Shapes of the serialized values, they must follow the `Wasm*` structs
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &'static str = r#"
+export type WasmOperation =
+  | { kind: "empty"; from: number; to: null }
+  | { kind: "fill"; from: null; to: number }
+  | { kind: "pour"; from: number; to: number };
+
+export interface WasmStep {
+  from: string;
+  operation: WasmOperation | null;
+  label: string | null;
+  to: string | null;
+}
+
+export interface WasmPosition {
+  input: "from" | "to" | "state";
+  offset: number;
+}
+
+export interface WasmError {
+  code: string;
+  message: string;
+  position?: WasmPosition;
+}
+
+export interface WasmStats {
+  depth: number;
+  visited: number;
+}
+
+export interface WasmResult {
+  error: WasmError | null;
+  steps: WasmStep[];
+  stats?: WasmStats;
+}
+
+export interface SolveOptions {
+  solver?: string;
+  maxDepth?: number;
+  maxStates?: number;
+  stats?: boolean;
+}
+
+export interface WasmSolverInfo {
+  name: string;
+  description: string;
+  optimal: boolean;
+  memory: string;
+  features: string[];
+  default: boolean;
+}
+
+export interface WasmIssue {
+  severity: "error" | "warning";
+  code: string;
+  message: string;
+}
+
+export interface WasmCheck {
+  valid: boolean;
+  error: WasmError | null;
+  issues: WasmIssue[];
+}
+
+export interface WasmProgress {
+  depth: number;
+  visited: number;
+  frontier: number;
+}
+
+export interface WasmSave {
+  from: string;
+  to: string;
+  moves: WasmOperation[];
+}
+
+export type WasmSearchStep =
+  | { done: false; progress: WasmProgress }
+  | { done: true; result: WasmResult };
+"#;
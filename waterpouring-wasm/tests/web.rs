@@ -0,0 +1,277 @@
+// Run with `wasm-pack test --node`
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Array, Reflect};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+use waterpouring_wasm::{
+    check, list_solvers, solve, solve_with, svg_of_solution, svg_of_state, WasmGame, WasmSearch,
+};
+
+fn get(value: &JsValue, key: &str) -> JsValue {
+    Reflect::get(value, &JsValue::from_str(key)).expect("Should be an object")
+}
+
+fn items(value: &JsValue) -> Vec<JsValue> {
+    assert!(Array::is_array(value), "Should be an array");
+    Array::from(value).iter().collect()
+}
+
+// Check the `typeof` of each field, "null" for null values
+fn assert_shape(value: &JsValue, fields: &[(&str, &str)]) {
+    assert!(value.is_object(), "Should be an object");
+    for (key, expected) in fields {
+        let field = get(value, key);
+        let actual = if field.is_null() {
+            "null".to_string()
+        } else {
+            field.js_typeof().as_string().unwrap()
+        };
+        assert_eq!(&actual, expected, "type of {}", key);
+    }
+}
+
+fn assert_operation(operation: &JsValue) {
+    let kind = get(operation, "kind")
+        .as_string()
+        .expect("kind should be a string");
+    match kind.as_str() {
+        "empty" => assert_shape(operation, &[("from", "number"), ("to", "null")]),
+        "fill" => assert_shape(operation, &[("from", "null"), ("to", "number")]),
+        "pour" => assert_shape(operation, &[("from", "number"), ("to", "number")]),
+        _ => panic!("Unknown kind {}", kind),
+    }
+}
+
+fn assert_result(result: &JsValue) {
+    assert_shape(result, &[("steps", "object")]);
+    for (index, step) in items(&get(result, "steps")).iter().enumerate() {
+        if index == 0 {
+            assert_shape(
+                step,
+                &[
+                    ("from", "string"),
+                    ("operation", "null"),
+                    ("label", "null"),
+                    ("to", "null"),
+                ],
+            );
+        } else {
+            assert_shape(
+                step,
+                &[
+                    ("from", "string"),
+                    ("operation", "object"),
+                    ("label", "string"),
+                    ("to", "string"),
+                ],
+            );
+            assert_operation(&get(step, "operation"));
+        }
+    }
+}
+
+fn assert_error(result: &JsValue, code: &str) {
+    let error = get(result, "error");
+    assert_shape(&error, &[("code", "string"), ("message", "string")]);
+    assert_eq!(get(&error, "code").as_string().unwrap(), code);
+}
+
+#[wasm_bindgen_test]
+fn solve_gives_a_wasm_result() {
+    let result = solve("0/5, 0/3", "4/5, 0/3");
+
+    assert_shape(&result, &[("error", "null")]);
+    assert_result(&result);
+    assert_eq!(items(&get(&result, "steps")).len(), 8);
+}
+
+#[wasm_bindgen_test]
+fn solve_with_stats() {
+    let options = js_sys::JSON::parse(r#"{"solver": "imp", "stats": true}"#).unwrap();
+
+    let result = solve_with("0/5, 0/3", "4/5, 0/3", options);
+
+    assert_result(&result);
+    assert_shape(
+        &get(&result, "stats"),
+        &[("depth", "number"), ("visited", "number")],
+    );
+}
+
+#[wasm_bindgen_test]
+fn errors_have_a_code() {
+    let unknown = js_sys::JSON::parse(r#"{"solver": "plop"}"#).unwrap();
+
+    assert_error(
+        &solve_with("0/5, 0/3", "4/5, 0/3", unknown),
+        "UNKNOWN_SOLVER",
+    );
+    assert_error(&solve("0/8, 0/4, 0/2", "1/8, 0/4, 0/2"), "UNSOLVABLE");
+    assert_error(&solve("0/8, 0/4", "0/4"), "GLASS_COUNT_MISMATCH");
+
+    let result = solve("0/5, 3/a", "4/5, 0/3");
+    assert_error(&result, "INVALID_NUMBER");
+    let position = get(&get(&result, "error"), "position");
+    assert_shape(&position, &[("input", "string"), ("offset", "number")]);
+    assert_eq!(get(&position, "offset").as_f64(), Some(7.0));
+}
+
+#[wasm_bindgen_test]
+fn list_solvers_gives_infos() {
+    let solvers = items(&list_solvers());
+
+    assert_eq!(solvers.len(), 3);
+    for info in solvers {
+        assert_shape(
+            &info,
+            &[
+                ("name", "string"),
+                ("description", "string"),
+                ("optimal", "boolean"),
+                ("memory", "string"),
+                ("features", "object"),
+                ("default", "boolean"),
+            ],
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+fn check_gives_issues() {
+    let check = check("0/4, 0/2", "1/4, 0/2");
+
+    assert_shape(&check, &[("valid", "boolean"), ("error", "null")]);
+    for issue in items(&get(&check, "issues")) {
+        assert_shape(
+            &issue,
+            &[
+                ("severity", "string"),
+                ("code", "string"),
+                ("message", "string"),
+            ],
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+fn search_steps() {
+    let mut search = WasmSearch::new("0/5, 0/3", "4/5, 0/3", JsValue::UNDEFINED);
+
+    let first = search.step(1);
+    assert_shape(&first, &[("done", "boolean"), ("progress", "object")]);
+    assert_shape(
+        &get(&first, "progress"),
+        &[
+            ("depth", "number"),
+            ("visited", "number"),
+            ("frontier", "number"),
+        ],
+    );
+
+    let last = search.step(1_000);
+    assert_shape(&last, &[("done", "boolean"), ("result", "object")]);
+    assert_result(&get(&last, "result"));
+}
+
+#[wasm_bindgen_test]
+fn play_a_game() {
+    let mut game = WasmGame::new("0/5, 0/3", "2/5, 0/3").expect("Should be valid");
+
+    for operation in items(&game.available_operations()) {
+        assert_operation(&operation);
+    }
+    let hint = game.hint(None).expect("Should have a hint");
+    assert_operation(&hint);
+    assert_eq!(game.apply(hint).unwrap(), "5/5, 0/3");
+    let pour = js_sys::JSON::parse(r#"{"kind": "pour", "from": 0, "to": 1}"#).unwrap();
+    game.apply(pour).unwrap();
+    assert!(game.can_undo());
+    assert_operation(&game.undo());
+    assert_operation(&game.redo());
+    assert!(game.redo().is_null());
+
+    let save = game.save();
+    assert_shape(
+        &save,
+        &[("from", "string"), ("to", "string"), ("moves", "object")],
+    );
+    let loaded = WasmGame::load(save).expect("Should load the save");
+    assert_eq!(loaded.state(), "2/5, 3/3");
+    assert!(!loaded.is_solved());
+}
+
+#[wasm_bindgen_test]
+fn invalid_moves_are_thrown() {
+    let mut game = WasmGame::new("0/5, 0/3", "2/5, 0/3").unwrap();
+    let empty = js_sys::JSON::parse(r#"{"kind": "empty", "from": 0, "to": null}"#).unwrap();
+
+    let error = game.apply(empty.clone()).unwrap_err();
+
+    assert_eq!(get(&error, "code").as_string().unwrap(), "NO_OP");
+    assert!(WasmGame::new("0/5", "plop").is_err());
+
+    let mut game = WasmGame::new("jug:0/5, cup:0/3", "2/5, 0/3").unwrap();
+    let error = game.apply(empty).unwrap_err();
+    assert_eq!(
+        get(&error, "message").as_string().unwrap(),
+        "Invalid move, step 0: Empty(jug) changes nothing"
+    );
+}
+
+#[wasm_bindgen_test]
+fn svg_are_strings() {
+    let state = svg_of_state("4/5, 0/3").unwrap();
+    assert!(state.starts_with("<svg "));
+
+    let animated = svg_of_solution("0/5, 0/3", "4/5, 0/3", None).unwrap();
+    assert!(animated.contains("<animate "));
+    let filmstrip = svg_of_solution("0/5, 0/3", "4/5, 0/3", Some(false)).unwrap();
+    assert!(!filmstrip.contains("<animate "));
+
+    let error = svg_of_state("4/5, x").unwrap_err();
+    assert_shape(
+        &get(&error, "position"),
+        &[("input", "string"), ("offset", "number")],
+    );
+    let error = svg_of_solution("0/4, 0/2", "1/4, 0/2", None).unwrap_err();
+    assert_eq!(get(&error, "code").as_string().unwrap(), "UNSOLVABLE");
+}
+
+#[wasm_bindgen_test]
+fn labelled_problems() {
+    let result = solve("jug:0/5L, cup:0/3L", "4/5, 0/3");
+
+    assert_result(&result);
+    let steps = items(&get(&result, "steps"));
+    assert_eq!(
+        get(&steps[0], "from").as_string().unwrap(),
+        "jug:0/5L, cup:0/3L"
+    );
+    assert_eq!(get(&steps[1], "label").as_string().unwrap(), "Fill(jug)");
+
+    let game = WasmGame::new("jug:0/5L, cup:0/3L", "4/5, 0/3").unwrap();
+    assert_eq!(game.state(), "jug:0/5L, cup:0/3L");
+    let error = get(&check("jug:0/5", "bucket:4/5"), "error");
+    assert_eq!(
+        get(&error, "code").as_string().unwrap(),
+        "CONFLICTING_LABELS"
+    );
+    // both states are valid, the error has no position
+    assert!(get(&error, "position").is_undefined());
+}
+
+#[wasm_bindgen_test]
+fn decimal_problems() {
+    let result = solve("0/2.5, 0/0.75", "1/2.5, 0/0.75");
+
+    assert_result(&result);
+    let steps = items(&get(&result, "steps"));
+    assert_eq!(get(&steps[0], "from").as_string().unwrap(), "0/2.5, 0/0.75");
+    let error = check("0/5000000000", "0/1");
+    assert_eq!(
+        get(&get(&error, "error"), "code").as_string().unwrap(),
+        "PRECISION_OVERFLOW"
+    );
+}
@@ -1,12 +1,71 @@
-import {solve} from "waterpouring-wasm";
+import {check, listSolvers, Search, solveWith} from "waterpouring-wasm";
 
 const waterpouring = document.getElementById("waterpouring");
 const output = document.querySelector("output");
+const issues = document.querySelector(".issues");
+const progress = document.querySelector("progress");
+
+// number of states expanded between two frames
+const BUDGET = 500;
+
+for (const info of listSolvers()) {
+  const option = new Option(`${info.name} - ${info.description}`, info.name, info.default, info.default);
+  waterpouring.solver.add(option);
+}
+
+const validate = () => {
+  const result = check(waterpouring.from.value, waterpouring.to.value);
+  issues.innerHTML = "";
+  if (result.error) {
+    const item = document.createElement("li");
+    item.textContent = result.error.message;
+    issues.appendChild(item);
+    const input = waterpouring[result.error.position.input];
+    input.focus();
+    input.setSelectionRange(result.error.position.offset, input.value.length);
+  }
+  for (const issue of result.issues) {
+    const item = document.createElement("li");
+    item.textContent = `${issue.severity}: ${issue.message}`;
+    issues.appendChild(item);
+  }
+  return result.valid;
+};
+
+waterpouring.from.addEventListener('change', validate);
+waterpouring.to.addEventListener('change', validate);
 
 waterpouring.addEventListener('submit', event => {
   event.preventDefault();
-  const result = solve(waterpouring.from.value, waterpouring.to.value);
-  console.log({result});
-  output.textContent = JSON.stringify(result, null, 2);
+  if (!validate()) {
+    return false;
+  }
+  const maxDepth = waterpouring.maxDepth.value ? Number(waterpouring.maxDepth.value) : undefined;
+  if (!waterpouring.incremental.checked) {
+    const result = solveWith(waterpouring.from.value, waterpouring.to.value, {
+      solver: waterpouring.solver.value,
+      maxDepth,
+      stats: true,
+    });
+    console.log({result});
+    output.textContent = JSON.stringify(result, null, 2);
+    return false;
+  }
+
+  const search = new Search(waterpouring.from.value, waterpouring.to.value, {maxDepth, stats: true});
+  progress.hidden = false;
+  const run = () => {
+    const step = search.step(BUDGET);
+    if (step.done) {
+      search.free();
+      progress.hidden = true;
+      console.log({result: step.result});
+      output.textContent = JSON.stringify(step.result, null, 2);
+    } else {
+      output.textContent = `Depth ${step.progress.depth}, ${step.progress.visited} states visited`;
+      requestAnimationFrame(run);
+    }
+  };
+  requestAnimationFrame(run);
   return false;
 });
@@ -0,0 +1,7 @@
+#!/usr/bin/env bash
+# Requires the `wasm32-unknown-unknown` target and `wasm-bindgen-cli` (same version as the `wasm-bindgen` crate)
+
+cargo build --release --target wasm32-unknown-unknown -p waterpouring-wasm2 || exit
+wasm-bindgen --target web --no-typescript --out-dir pkg ../target/wasm32-unknown-unknown/release/waterpouring_wasm2.wasm || exit
+
+echo "Serve with: python3 -m http.server, then open http://localhost:8000/waterpouring.html"
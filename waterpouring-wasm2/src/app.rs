@@ -0,0 +1,64 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{Document, Event, HtmlFormElement, HtmlInputElement};
+
+use crate::{render_problem, render_solution};
+
+fn element<T: JsCast>(document: &Document, selector: &str) -> Result<T, JsValue> {
+    document
+        .query_selector(selector)?
+        .ok_or_else(|| JsValue::from_str(&format!("Missing element {}", selector)))?
+        .dyn_into::<T>()
+        .map_err(|_| JsValue::from_str(&format!("Unexpected element {}", selector)))
+}
+
+// Each problem is a radio button with `data-from` and `data-to`
+pub(crate) fn init(document: &Document) -> Result<(), JsValue> {
+    let radios = document.query_selector_all("input[name=problem]")?;
+    for index in 0..radios.length() {
+        let radio = match radios
+            .get(index)
+            .and_then(|node| node.dyn_into::<HtmlInputElement>().ok())
+        {
+            Some(radio) => radio,
+            None => continue,
+        };
+        if let Some(label) = radio.next_element_sibling() {
+            let (from, to) = (data(&radio, "from"), data(&radio, "to"));
+            label.set_inner_html(&render_problem(&from, &to));
+        }
+    }
+
+    let form: HtmlFormElement = element(document, "#form")?;
+    let document = document.clone();
+    let on_submit = Closure::<dyn FnMut(Event)>::new(move |event: Event| {
+        event.prevent_default();
+        if let Err(error) = solve(&document) {
+            web_sys::console::error_1(&error);
+        }
+    });
+    form.add_event_listener_with_callback("submit", on_submit.as_ref().unchecked_ref())?;
+    // the listener lives as long as the page
+    on_submit.forget();
+
+    Ok(())
+}
+
+fn data(input: &HtmlInputElement, name: &str) -> String {
+    input
+        .get_attribute(&format!("data-{}", name))
+        .unwrap_or_default()
+}
+
+fn solve(document: &Document) -> Result<(), JsValue> {
+    let output: web_sys::Element = element(document, "#solution")?;
+    let html = match document.query_selector("input[name=problem]:checked")? {
+        Some(checked) => {
+            let radio: HtmlInputElement = checked.dyn_into()?;
+            render_solution(&data(&radio, "from"), &data(&radio, "to"), None)
+        }
+        None => crate::render::error_html("Select a problem first"),
+    };
+    output.set_inner_html(&html);
+    Ok(())
+}
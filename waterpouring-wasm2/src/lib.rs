@@ -1,4 +1,51 @@
+use wasm_bindgen::prelude::*;
 
-pub fn plop(s: &str) {
-    println!("Plop {} !", s)
+use waterpouring_model::problem::Problem;
+use waterpouring_solvers::registry;
+
+use crate::render::{error_html, problem_html, solution_html};
+
+mod app;
+pub mod render;
+
+// Glasses can be named, with a unit and decimal volumes, e.g. `jug:0/5L, cup:0/0.5L`
+fn parse(from: &str, to: &str) -> Result<Problem, String> {
+    Problem::parse(from, to).map_err(|err| err.to_string())
+}
+
+// HTML of the glasses of a problem
+#[wasm_bindgen(js_name = renderProblem)]
+pub fn render_problem(from: &str, to: &str) -> String {
+    match parse(from, to) {
+        Ok(problem) => problem_html(&problem),
+        Err(message) => error_html(&message),
+    }
+}
+
+// HTML of the animated solution, or of the error
+#[wasm_bindgen(js_name = renderSolution)]
+pub fn render_solution(from: &str, to: &str, solver: Option<String>) -> String {
+    let problem = match parse(from, to) {
+        Ok(problem) => problem,
+        Err(message) => return error_html(&message),
+    };
+    let entry = match &solver {
+        None => registry::default_solver(),
+        Some(name) => match registry::find(name) {
+            Some(entry) => entry,
+            None => return error_html(&format!("Unknown solver '{}'", name)),
+        },
+    };
+
+    let result = entry.create().solve(problem.clone());
+    solution_html(&problem, result)
+}
+
+// Wire the page when loaded in a browser, nothing to do elsewhere (e.g. tests under Node)
+#[wasm_bindgen(start)]
+pub fn start() -> Result<(), JsValue> {
+    match web_sys::window().and_then(|window| window.document()) {
+        Some(document) => app::init(&document),
+        None => Ok(()),
+    }
 }
@@ -0,0 +1,160 @@
+use waterpouring_model::glass::Glass;
+use waterpouring_model::labels::Labels;
+use waterpouring_model::operations::GlassId;
+use waterpouring_model::plan::Plan;
+use waterpouring_model::problem::Problem;
+use waterpouring_model::solver::SolverError::{InvalidProblem, LimitExceeded, UnsolvableProblem};
+use waterpouring_model::solver::SolverResult;
+use waterpouring_model::state::State;
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Sizes are computed by the CSS from `--capacity` and `--level`
+pub fn glass_html(index: GlassId, glass: &Glass, labels: &Labels) -> String {
+    let label = escape(&labels.glass(index, glass));
+    format!(
+        r#"<span class="glass" style="--capacity: {}; --level: {}" title="{}"><span class="water"></span><span class="label">{}</span></span>"#,
+        labels.number(glass.capacity),
+        labels.number(glass.current),
+        label,
+        label
+    )
+}
+
+pub fn state_html(state: &State, labels: &Labels) -> String {
+    let glasses: String = state
+        .iter()
+        .enumerate()
+        .map(|(index, glass)| glass_html(index, glass, labels))
+        .collect();
+    format!(r#"<span class="state">{}</span>"#, glasses)
+}
+
+pub fn problem_html(problem: &Problem) -> String {
+    format!(
+        r#"<span class="problem">{}{}</span>"#,
+        state_html(&problem.from, &problem.labels),
+        state_html(&problem.to, &problem.labels)
+    )
+}
+
+pub fn error_html(message: &str) -> String {
+    format!(r#"<p class="error">{}</p>"#, escape(message))
+}
+
+// Steps appear one after the other, `--step` gives the animation delay
+pub fn solution_html(problem: &Problem, result: SolverResult) -> String {
+    let operations = match result {
+        Ok(operations) => operations,
+        Err(InvalidProblem { reason, .. }) => {
+            return error_html(&format!("Cannot solve because {}", reason))
+        }
+        Err(UnsolvableProblem { .. }) => return error_html("No solution found!"),
+        Err(error @ LimitExceeded { .. }) => return error_html(&error.to_string()),
+    };
+    let trace = match Plan::new(problem.clone(), operations).validate() {
+        Ok(trace) => trace,
+        Err(error) => {
+            let message = format!("Invalid solution, {}", error.describe(&problem.labels));
+            return error_html(&message);
+        }
+    };
+
+    let mut items = vec![format!(
+        r#"<li class="step" style="--step: 0"><span class="operation">Start</span>{}</li>"#,
+        state_html(trace.start(), trace.labels())
+    )];
+    for (index, step) in trace.iter().enumerate() {
+        items.push(format!(
+            r#"<li class="step" style="--step: {}"><span class="operation">{}</span>{}</li>"#,
+            index + 1,
+            escape(&trace.labels().operation(&step.operation)),
+            state_html(&step.after, trace.labels())
+        ));
+    }
+
+    format!(
+        r#"<p class="summary">{} operations</p><ol class="solution">{}</ol>"#,
+        trace.len(),
+        items.concat()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use waterpouring_model::operations::Operation;
+    use waterpouring_model::solver::Evidence::Exhausted;
+
+    use super::*;
+
+    #[test]
+    fn render_glass() {
+        let html = glass_html(0, &Glass::new(4, 5), &Labels::default());
+
+        assert_eq!(
+            html,
+            r#"<span class="glass" style="--capacity: 5; --level: 4" title="4/5"><span class="water"></span><span class="label">4/5</span></span>"#
+        );
+    }
+
+    #[test]
+    fn render_solution() {
+        let problem = Problem::from(("0/5, 0/3", "2/5, 0/3"));
+        let operations = vec![
+            Operation::fill(0),
+            Operation::pour(0, 1),
+            Operation::empty(1),
+        ];
+
+        let html = solution_html(&problem, Ok(operations));
+
+        assert!(html.starts_with(r#"<p class="summary">3 operations</p>"#));
+        assert_eq!(html.matches(r#"<li class="step""#).count(), 4);
+        assert!(html.contains(r#"--step: 3"><span class="operation">Empty(1)</span>"#));
+    }
+
+    #[test]
+    fn render_labelled_solution() {
+        let problem = Problem::from(("jug:0/5L, cup:0/3L", "2/5, 3/3"));
+        let operations = vec![Operation::fill(0), Operation::pour(0, 1)];
+
+        let html = solution_html(&problem, Ok(operations));
+
+        assert!(html.contains(r#"<span class="operation">Pour(jug-&gt;cup)</span>"#));
+        assert!(html.contains(r#"title="cup:3/3L""#));
+    }
+
+    #[test]
+    fn render_decimal_glass() {
+        let labels = Labels::default().with_decimals(2);
+
+        let html = glass_html(0, &Glass::new(50, 75), &labels);
+
+        assert!(html.contains(r#"style="--capacity: 0.75; --level: 0.5" title="0.5/0.75""#));
+    }
+
+    #[test]
+    fn render_error() {
+        let problem = Problem::from(("0/4, 0/2", "1/4, 0/2"));
+        let error = UnsolvableProblem {
+            problem: problem.to_string(),
+            evidence: Some(Exhausted { visited: 6 }),
+        };
+
+        let html = solution_html(&problem, Err(error));
+
+        assert_eq!(html, r#"<p class="error">No solution found!</p>"#);
+    }
+
+    #[test]
+    fn escape_html() {
+        assert_eq!(escape("0/5 -> 1/5 & <b>"), "0/5 -&gt; 1/5 &amp; &lt;b&gt;");
+    }
+}
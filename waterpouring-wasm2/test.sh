@@ -0,0 +1,4 @@
+#!/usr/bin/env bash
+# Requires `wasm-bindgen-cli`, tests run with Node
+
+CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUNNER=wasm-bindgen-test-runner cargo test --target wasm32-unknown-unknown
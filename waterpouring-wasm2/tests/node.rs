@@ -0,0 +1,39 @@
+// Run with `./test.sh`
+#![cfg(target_arch = "wasm32")]
+
+use wasm_bindgen_test::*;
+
+use waterpouring_wasm2::{render_problem, render_solution, start};
+
+#[wasm_bindgen_test]
+fn start_without_document() {
+    assert!(start().is_ok());
+}
+
+#[wasm_bindgen_test]
+fn render_a_problem() {
+    let html = render_problem("0/5, 0/3", "4/5, 0/3");
+
+    assert!(html.starts_with(r#"<span class="problem">"#));
+    assert_eq!(html.matches(r#"class="glass""#).count(), 4);
+}
+
+#[wasm_bindgen_test]
+fn render_a_solution() {
+    let html = render_solution("0/5, 0/3", "4/5, 0/3", None);
+
+    assert!(html.starts_with(r#"<p class="summary">7 operations</p>"#));
+    assert_eq!(html.matches(r#"<li class="step""#).count(), 8);
+}
+
+#[wasm_bindgen_test]
+fn render_errors() {
+    let invalid = render_solution("0/5, 0/3", "4/5, x", None);
+    assert!(invalid.starts_with(r#"<p class="error">Invalid 'to' state"#));
+
+    let conflict = render_solution("jug:0/5, 0/3", "cup:4/5, 0/3", None);
+    assert!(conflict.starts_with(r#"<p class="error">Invalid problem, glass 0"#));
+
+    let unknown = render_solution("0/5, 0/3", "4/5, 0/3", Some("plop".into()));
+    assert_eq!(unknown, r#"<p class="error">Unknown solver 'plop'</p>"#);
+}